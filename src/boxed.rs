@@ -0,0 +1,163 @@
+//! Runtime-sized prime generation and checking, for callers who don't know
+//! their bit length at compile time (negotiated RSA modulus sizes, etc.).
+//! Mirrors [`crate::common`]'s algorithms over [`BoxedUint`] instead of the
+//! const-generic `UInt<L>`, trading the Montgomery fast path there for an
+//! allocation that can be sized at runtime.
+
+use crypto_bigint::{BoxedUint, CheckedSub, NonZero, Random, Zero};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::common::{resolve_bit_length_boxed, PRIMES};
+use crate::error::Error;
+
+/// Generates a new prime number of `bit_length` bits, heap-allocated at
+/// exactly that width rather than a fixed `UInt<L>`.
+pub fn gen_prime<R: CryptoRng + RngCore + ?Sized>(
+    bit_length: usize,
+    rng: &mut R,
+) -> Result<BoxedUint, Error> {
+    let bit_length = resolve_bit_length_boxed(bit_length)?;
+
+    loop {
+        let candidate = random_odd_of_bit_length(bit_length, rng);
+        if is_prime(&candidate, rng) {
+            return Ok(candidate);
+        }
+    }
+}
+
+/// Test if a heap-allocated candidate is prime by trial division against
+/// the first 2048 primes, a Fermat test, then `log2(bitlength) + 5` rounds
+/// of Miller-Rabin, the same structure as [`crate::common::is_prime`].
+pub fn is_prime<R: CryptoRng + RngCore + ?Sized>(candidate: &BoxedUint, rng: &mut R) -> bool {
+    let two = BoxedUint::from(2_u32).widen(candidate.bits_precision());
+
+    if candidate == &two {
+        return true;
+    }
+    if bool::from(candidate.is_even()) || candidate < &two {
+        return false;
+    }
+
+    for &p in PRIMES.iter() {
+        let modulus = NonZero::new(BoxedUint::from(p).widen(candidate.bits_precision())).unwrap();
+        let r = candidate.rem(&modulus);
+        if bool::from(r.is_zero()) {
+            return candidate == modulus.as_ref();
+        }
+    }
+
+    let checks = crate::common::required_checks(candidate.bits() as usize);
+    fermat(candidate, rng) && miller_rabin(candidate, checks, rng)
+}
+
+fn fermat<R: CryptoRng + RngCore + ?Sized>(candidate: &BoxedUint, rng: &mut R) -> bool {
+    let modulus = NonZero::new(candidate.clone()).unwrap();
+    let one = BoxedUint::one().widen(candidate.bits_precision());
+    let exp = candidate.checked_sub(&one).unwrap();
+    let base = random_below(candidate, rng);
+
+    mod_pow(&base, &exp, &modulus) == one
+}
+
+fn miller_rabin<R: CryptoRng + RngCore + ?Sized>(
+    candidate: &BoxedUint,
+    rounds: usize,
+    rng: &mut R,
+) -> bool {
+    let one = BoxedUint::one().widen(candidate.bits_precision());
+    let cand_minus_one = candidate.checked_sub(&one).unwrap();
+    let modulus = NonZero::new(candidate.clone()).unwrap();
+
+    let mut d = cand_minus_one.clone();
+    let mut trials = 0_usize;
+    while bool::from(d.is_even()) {
+        d = d.shr(1);
+        trials += 1;
+    }
+    if trials < 5 {
+        trials = 5;
+    }
+
+    'nextbasis: for _ in 0..rounds {
+        let basis = random_below(candidate, rng);
+        let mut test = mod_pow(&basis, &d, &modulus);
+
+        if test == one || test == cand_minus_one {
+            continue;
+        }
+        for _ in 1..trials - 1 {
+            test = test.mul_mod(&test, &modulus);
+            if test == one {
+                return false;
+            } else if test == cand_minus_one {
+                continue 'nextbasis;
+            }
+        }
+        return false;
+    }
+
+    true
+}
+
+fn mod_pow(base: &BoxedUint, exp: &BoxedUint, modulus: &NonZero<BoxedUint>) -> BoxedUint {
+    let mut result = BoxedUint::one().widen(base.bits_precision());
+    let mut base = base.rem(modulus);
+
+    for i in (0..exp.bits()).rev() {
+        result = result.mul_mod(&result, modulus);
+        if bool::from(exp.bit(i)) {
+            result = result.mul_mod(&base, modulus);
+        }
+    }
+    let _ = &mut base;
+
+    result
+}
+
+fn random_below<R: CryptoRng + RngCore + ?Sized>(bound: &BoxedUint, rng: &mut R) -> BoxedUint {
+    let modulus = NonZero::new(bound.clone()).unwrap();
+    BoxedUint::random(rng, bound.bits_precision()).rem(&modulus)
+}
+
+fn random_odd_of_bit_length<R: CryptoRng + RngCore + ?Sized>(
+    bit_length: usize,
+    rng: &mut R,
+) -> BoxedUint {
+    let precision = bit_length as u32;
+    let mut candidate = BoxedUint::random(rng, precision);
+    candidate = candidate.bitor(&(BoxedUint::one().widen(precision) << (bit_length - 1)));
+    candidate = candidate.bitor(&BoxedUint::one().widen(precision));
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mod_pow;
+    use crypto_bigint::{BoxedUint, NonZero};
+
+    /// 2047 = 23 * 89 is the smallest strong pseudoprime to base 2: both the
+    /// Fermat test and every Miller-Rabin round "pass" it when base 2 is the
+    /// witness, despite it being composite. `check_boxed`/`is_prime` still
+    /// reject it via trial division (23 is well within the first 2048
+    /// primes), but that's incidental to this candidate's small size — the
+    /// Fermat/Miller-Rabin stage itself, the only stage this backend runs
+    /// after trial division, cannot tell this case from a real prime. That gap is exactly why
+    /// [`crate::generator::StrongPrimeCandidate`] has no `BoxedUint` impl.
+    #[test]
+    fn base_2_strong_pseudoprime_fools_fermat_and_miller_rabin() {
+        let n = BoxedUint::from(2047_u32).widen(32);
+        let modulus = NonZero::new(n).unwrap();
+        let base = BoxedUint::from(2_u32).widen(32);
+        let one = BoxedUint::one().widen(32);
+
+        // Fermat: 2^2046 mod 2047 == 1.
+        let n_minus_one = BoxedUint::from(2046_u32).widen(32);
+        assert_eq!(mod_pow(&base, &n_minus_one, &modulus), one);
+
+        // Miller-Rabin: 2046 = 2^1 * 1023, and 2^1023 mod 2047 == 1, so the
+        // base-2 witness never gets a chance to reveal the factorization.
+        let d = BoxedUint::from(1023_u32).widen(32);
+        assert_eq!(mod_pow(&base, &d, &modulus), one);
+    }
+}
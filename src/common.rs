@@ -1,91 +1,634 @@
-use num_bigint::{BigInt, BigUint, RandBigInt, Sign};
-use num_integer::Integer;
-use num_traits::identities::{One, Zero};
-use num_traits::Signed;
+use crypto_bigint::{CheckedSub, NonZero, Random, UInt, Zero};
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
 
 use crate::error::{Error, Result};
-use crate::rand::Randoms;
-use lazy_static::lazy_static;
-use rand::thread_rng;
-use rand::Rng;
 
 pub const MIN_BIT_LENGTH: usize = 128;
 
-/// Create a new prime number with size `bit_length` sourced
-/// from an already-initialized `Rng`
-pub fn gen_prime<R: Rng + ?Sized>(bit_length: usize, rng: &mut R) -> Result {
-    if bit_length < MIN_BIT_LENGTH {
-        Err(Error::BitLength(bit_length))
+/// Create a new prime number with size `bit_length` sourced from an
+/// already-initialized `Rng`. `bit_length` of `None` means "the full width
+/// of `UInt<L>`", in which case the top bit is forced so the result is
+/// exactly `L*64` bits; the `MIN_BIT_LENGTH` floor only applies when a size
+/// is given explicitly.
+pub fn gen_prime<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    bit_length: Option<usize>,
+    rng: &mut R,
+) -> Result<L> {
+    let bit_length = resolve_bit_length::<L>(bit_length)?;
+
+    for candidate in Sieve::new(bit_length, rng) {
+        if is_prime(&candidate, rng) {
+            return Ok(candidate);
+        }
+    }
+
+    unreachable!("Sieve never terminates")
+}
+
+/// Constructs a new safe prime with the size of `bit_length` bits, sourced
+/// from an already-initialized `Rng`. See [`gen_prime`] for the meaning of
+/// `bit_length: None`.
+pub fn gen_safe_prime<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    bit_length: Option<usize>,
+    rng: &mut R,
+) -> Result<L> {
+    let bit_length = resolve_bit_length::<L>(bit_length)?;
+
+    for candidate in Sieve::new_safe(bit_length, rng) {
+        if is_safe_prime(&candidate, rng) {
+            return Ok(candidate);
+        }
+    }
+
+    unreachable!("Sieve never terminates")
+}
+
+/// Resolve an optional requested bit length against the width of `UInt<L>`:
+/// `None` picks the full width (skipping the `MIN_BIT_LENGTH` floor), while
+/// `Some(n)` keeps today's minimum-size check.
+pub(crate) fn resolve_bit_length<const L: usize>(
+    bit_length: Option<usize>,
+) -> core::result::Result<usize, Error> {
+    match bit_length {
+        None => Ok(UInt::<L>::BITS as usize),
+        Some(bit_length) if bit_length < MIN_BIT_LENGTH => Err(Error::BitLength(bit_length)),
+        Some(bit_length) => Ok(bit_length),
+    }
+}
+
+/// Controls how many Miller-Rabin rounds a primality check runs and which
+/// bases it runs them with, for callers who need a specific security level
+/// or reproducible witnesses instead of `required_checks`' `log2(bits) + 5`
+/// random rounds.
+#[derive(Clone, Debug, Default)]
+pub struct PrimalityConfig<const L: usize> {
+    /// Number of random Miller-Rabin rounds to run after `extra_bases`.
+    /// `None` falls back to `required_checks(bit_length)`.
+    pub mr_rounds: Option<usize>,
+    /// Always test base 2, even when it isn't present in `extra_bases`.
+    pub force_base_2: bool,
+    /// Specific witnesses to test before any random ones. With `mr_rounds`
+    /// set to `Some(0)` these are the only Miller-Rabin witnesses; `None`
+    /// still adds `required_checks` random rounds after them.
+    pub extra_bases: Vec<UInt<L>>,
+}
+
+impl<const L: usize> PrimalityConfig<L> {
+    fn rounds(&self, candidate: &UInt<L>) -> usize {
+        self.mr_rounds
+            .unwrap_or_else(|| required_checks(bit_length(candidate)))
+    }
+
+    /// Builds a config whose Miller-Rabin stage only tests `bases`, running
+    /// no additional random rounds afterward; the Fermat test that runs
+    /// before Miller-Rabin still draws one random base. Handy for
+    /// regression tests that need to pin down an exact witness set, e.g.
+    /// against known Carmichael-style pseudoprimes.
+    pub fn deterministic(bases: Vec<UInt<L>>) -> Self {
+        PrimalityConfig {
+            mr_rounds: Some(0),
+            force_base_2: false,
+            extra_bases: bases,
+        }
+    }
+}
+
+/// Like [`is_prime`], but runs Miller-Rabin with the rounds and bases given
+/// by `config` instead of the library's default choices.
+pub fn is_prime_with<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    candidate: &UInt<L>,
+    config: &PrimalityConfig<L>,
+    rng: &mut R,
+) -> bool {
+    _is_prime(
+        candidate,
+        config.rounds(candidate),
+        config.force_base_2,
+        &config.extra_bases,
+        rng,
+    )
+}
+
+/// Like [`is_safe_prime`], but runs Miller-Rabin with the rounds and bases
+/// given by `config` instead of the library's default choices.
+pub fn is_safe_prime_with<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    candidate: &UInt<L>,
+    config: &PrimalityConfig<L>,
+    rng: &mut R,
+) -> bool {
+    _is_safe_prime(
+        candidate,
+        config.rounds(candidate),
+        config.force_base_2,
+        &config.extra_bases,
+        rng,
+    )
+}
+
+/// Like [`gen_prime`], but checks each candidate with [`is_prime_with`]
+/// against `config` instead of [`is_prime`]'s defaults.
+pub fn gen_prime_with<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    bit_length: Option<usize>,
+    rng: &mut R,
+    config: &PrimalityConfig<L>,
+) -> Result<L> {
+    let bit_length = resolve_bit_length::<L>(bit_length)?;
+
+    for candidate in Sieve::new(bit_length, rng) {
+        if is_prime_with(&candidate, config, rng) {
+            return Ok(candidate);
+        }
+    }
+
+    unreachable!("Sieve never terminates")
+}
+
+/// Like [`gen_safe_prime`], but checks each candidate with
+/// [`is_safe_prime_with`] against `config` instead of [`is_safe_prime`]'s
+/// defaults.
+pub fn gen_safe_prime_with<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    bit_length: Option<usize>,
+    rng: &mut R,
+    config: &PrimalityConfig<L>,
+) -> Result<L> {
+    let bit_length = resolve_bit_length::<L>(bit_length)?;
+
+    for candidate in Sieve::new_safe(bit_length, rng) {
+        if is_safe_prime_with(&candidate, config, rng) {
+            return Ok(candidate);
+        }
+    }
+
+    unreachable!("Sieve never terminates")
+}
+
+/// Generates an RSA prime pair `(p, q)`, each `modulus_bits / 2` bits, ready
+/// to build a `modulus_bits`-bit RSA modulus with public exponent
+/// `public_exponent`: `p != q`, `|p - q| > 2^(modulus_bits/2 - 100)` per the
+/// FIPS 186-4 prime-distance requirement, and `gcd(p-1, e) = gcd(q-1, e) = 1`
+/// so `lambda(n)` is invertible mod `e`.
+pub fn gen_rsa_primes<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    modulus_bits: usize,
+    public_exponent: &UInt<L>,
+    rng: &mut R,
+) -> core::result::Result<(UInt<L>, UInt<L>), Error> {
+    let prime_bits = modulus_bits / 2;
+    // The FIPS 186-4 prime-distance bound `2^(nlen/2 - 100)` only makes
+    // sense once `prime_bits` clears 100; reject smaller requests instead of
+    // underflowing the shift amount below.
+    if prime_bits <= 100 {
+        return Err(Error::BitLength(modulus_bits));
+    }
+    let min_distance = UInt::<L>::ONE << (prime_bits - 100);
+
+    let p = loop {
+        let candidate = gen_prime::<L, R>(Some(prime_bits), rng)?;
+        if is_coprime_to_exponent(&candidate, public_exponent) {
+            break candidate;
+        }
+    };
+
+    let q = loop {
+        let candidate = gen_prime::<L, R>(Some(prime_bits), rng)?;
+        if candidate == p || !is_coprime_to_exponent(&candidate, public_exponent) {
+            continue;
+        }
+
+        let distance = if candidate >= p {
+            candidate.checked_sub(&p).unwrap()
+        } else {
+            p.checked_sub(&candidate).unwrap()
+        };
+        if distance <= min_distance {
+            continue;
+        }
+
+        break candidate;
+    };
+
+    Ok((p, q))
+}
+
+/// Whether `gcd(candidate - 1, e) = 1`, i.e. `candidate` can be an RSA prime
+/// factor for public exponent `e`.
+fn is_coprime_to_exponent<const L: usize>(candidate: &UInt<L>, e: &UInt<L>) -> bool {
+    let minus_one = candidate.checked_sub(&UInt::<L>::ONE).unwrap();
+    gcd(&minus_one, e) == UInt::<L>::ONE
+}
+
+/// Finds the smallest prime strictly greater than `n`.
+pub fn next_prime<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    n: &UInt<L>,
+    rng: &mut R,
+) -> UInt<L> {
+    if *n < UInt::<L>::from(2_u64) {
+        return UInt::<L>::from(2_u64);
+    }
+    search_forward(round_up_to_odd_above(n), false, rng)
+}
+
+/// Finds the smallest safe prime strictly greater than `n`.
+pub fn next_safe_prime<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    n: &UInt<L>,
+    rng: &mut R,
+) -> UInt<L> {
+    search_forward(round_up_to_odd_above(n), true, rng)
+}
+
+/// Finds the largest prime strictly less than `n`. Errors if `n <= 2`, since
+/// there is no such prime.
+pub fn prev_prime<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    n: &UInt<L>,
+    rng: &mut R,
+) -> Result<L> {
+    if *n <= UInt::<L>::from(2_u64) {
+        return Err(Error::NoPrecedingPrime);
+    }
+    if *n == UInt::<L>::from(3_u64) {
+        return Ok(UInt::<L>::from(2_u64));
+    }
+
+    Ok(search_backward(round_down_to_odd_below(n), rng))
+}
+
+fn round_up_to_odd_above<const L: usize>(n: &UInt<L>) -> UInt<L> {
+    if bool::from(n.is_even()) {
+        n.wrapping_add(&UInt::<L>::ONE)
     } else {
-        let checks = required_checks(bit_length);
-        let mut candidate;
-        let size = bit_length as u64;
+        n.wrapping_add(&UInt::<L>::from(2_u64))
+    }
+}
 
-        loop {
-            candidate = rng.gen_biguint(bit_length as u64);
+fn round_down_to_odd_below<const L: usize>(n: &UInt<L>) -> UInt<L> {
+    if bool::from(n.is_even()) {
+        n.checked_sub(&UInt::<L>::ONE).unwrap()
+    } else {
+        n.checked_sub(&UInt::<L>::from(2_u64)).unwrap()
+    }
+}
 
-            //Set lowest bit
-            candidate |= BigUint::one();
-            while candidate.bits() < size {
-                candidate <<= 1;
-                candidate |= BigUint::one();
+/// Walks `candidate` upward by 2, maintaining its residue modulo every
+/// `PRIMES` entry incrementally (the same wheel [`Sieve`] sieves a window
+/// of at once), and validates the first survivor with the full
+/// Baillie-PSW test. Shared by [`next_prime`] and [`next_safe_prime`].
+fn search_forward<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    start: UInt<L>,
+    safe: bool,
+    rng: &mut R,
+) -> UInt<L> {
+    let mut candidate = start;
+    let mut residues: Vec<u32> = PRIMES.iter().map(|&p| rem_small(&candidate, p)).collect();
+    let mut half_residues = if safe {
+        let half = candidate >> 1;
+        Some(PRIMES.iter().map(|&p| rem_small(&half, p)).collect::<Vec<_>>())
+    } else {
+        None
+    };
+
+    loop {
+        let clean = residues
+            .iter()
+            .zip(PRIMES.iter())
+            .all(|(&r, &p)| r != 0 || p == 2 || candidate == UInt::<L>::from(p as u64))
+            && half_residues
+                .as_ref()
+                .map(|half| {
+                    let h = candidate >> 1;
+                    half.iter()
+                        .zip(PRIMES.iter())
+                        .all(|(&r, &p)| r != 0 || h == UInt::<L>::from(p as u64))
+                })
+                .unwrap_or(true);
+
+        if clean {
+            let accept = if safe {
+                is_safe_prime_baillie_psw(&candidate, rng)
+            } else {
+                is_prime_baillie_psw(&candidate, rng)
+            };
+            if accept {
+                return candidate;
             }
+        }
 
-            if _is_prime(&candidate, checks, true) && lucas(&candidate) {
-                return Ok(candidate);
+        candidate = candidate.wrapping_add(&UInt::<L>::from(2_u64));
+        for (residue, &p) in residues.iter_mut().zip(PRIMES.iter()) {
+            *residue += 2;
+            if *residue >= p {
+                *residue -= p;
+            }
+        }
+        if let Some(half_residues) = &mut half_residues {
+            for (residue, &p) in half_residues.iter_mut().zip(PRIMES.iter()) {
+                *residue += 1;
+                if *residue >= p {
+                    *residue -= p;
+                }
             }
         }
     }
 }
 
-/// Constructs a new `SafePrime` with the size of `bit_length` bits, sourced
-/// from an already-initialized `Rng`.
-pub fn gen_safe_prime<R: Rng + ?Sized>(bit_length: usize, rng: &mut R) -> Result {
-    let two = BigUint::from(2_u8);
-    let three = BigUint::from(3_u8);
-    if bit_length < MIN_BIT_LENGTH {
-        Err(Error::BitLength(bit_length))
-    } else {
-        let mut candidate: BigUint;
-        let checks = required_checks(bit_length) - 5;
+/// Walks `candidate` downward by 2, maintaining its residue modulo every
+/// `PRIMES` entry incrementally, and validates the first survivor with the
+/// full Baillie-PSW test. Shared entry point for [`prev_prime`].
+fn search_backward<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    start: UInt<L>,
+    rng: &mut R,
+) -> UInt<L> {
+    let mut candidate = start;
+    let mut residues: Vec<u32> = PRIMES.iter().map(|&p| rem_small(&candidate, p)).collect();
 
-        loop {
-            candidate = gen_prime(bit_length, rng)?;
+    loop {
+        let clean = residues
+            .iter()
+            .zip(PRIMES.iter())
+            .all(|(&r, &p)| r != 0 || p == 2 || candidate == UInt::<L>::from(p as u64));
+
+        if clean && is_prime_baillie_psw(&candidate, rng) {
+            return candidate;
+        }
 
-            if (&candidate % &three) == two && _is_prime(&(&candidate >> 1), checks, true) {
-                break;
+        candidate = candidate.checked_sub(&UInt::<L>::from(2_u64)).unwrap();
+        for (residue, &p) in residues.iter_mut().zip(PRIMES.iter()) {
+            if *residue < 2 {
+                *residue += p;
             }
+            *residue -= 2;
+        }
+    }
+}
+
+/// Bit length at or below which [`is_prime_deterministic`] is guaranteed to
+/// return `Some` (its largest threshold lies between `2^81` and `2^82`),
+/// used as the base case of [`gen_provable_prime`]'s recursion instead of a
+/// further Pocklington step.
+const PROVABLE_BASE_BITS: usize = 80;
+
+/// One level of a [`Certificate`]: `p = 2*r*q + 1` is proved prime given
+/// that `q` is prime, via Pocklington's theorem witnessed by base `a`.
+#[derive(Clone, Debug)]
+pub struct CertificateStep<const L: usize> {
+    /// The prime being proved at this level.
+    pub p: UInt<L>,
+    /// The already-proved (or base-case) prime factor of `p - 1`.
+    pub q: UInt<L>,
+    /// The cofactor such that `p = 2*r*q + 1`.
+    pub r: UInt<L>,
+    /// The Pocklington witness base.
+    pub a: UInt<L>,
+}
+
+/// A Maurer/Pocklington certificate proving a prime is prime without relying
+/// on any probabilistic test. Steps run from the generated prime down to a
+/// base prime small enough for [`is_prime_deterministic`] to settle
+/// directly; see [`gen_provable_prime`] and [`verify_certificate`].
+#[derive(Clone, Debug, Default)]
+pub struct Certificate<const L: usize> {
+    /// Steps from the top-level prime down to the base case, in order.
+    pub steps: Vec<CertificateStep<L>>,
+}
+
+/// Generates a prime of `bit_length` bits together with a certificate
+/// proving its primality, using Maurer's recursive construction: a prime
+/// factor `q` of roughly half the bit length is generated first (down to a
+/// base case small enough to settle with [`is_prime_deterministic`]), then
+/// candidates `p = 2*r*q + 1` are searched until one, together with a
+/// witness base `a`, satisfies Pocklington's theorem.
+pub fn gen_provable_prime<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    bit_length: usize,
+    rng: &mut R,
+) -> (UInt<L>, Certificate<L>) {
+    if bit_length <= PROVABLE_BASE_BITS {
+        loop {
+            let candidate = random_odd_of_bit_length::<L, R>(bit_length, rng);
+            if is_prime_deterministic(&candidate) == Some(true) {
+                return (candidate, Certificate::default());
+            }
+        }
+    }
+
+    let (q, cert_q) = gen_provable_prime::<L, R>(bit_length / 2 + 1, rng);
+
+    loop {
+        let mut r = random_below(&q, rng);
+        if bool::from(r.is_odd()) {
+            r = r.checked_sub(&UInt::<L>::ONE).unwrap();
+        }
+
+        let p = r
+            .wrapping_mul(&q)
+            .wrapping_mul(&UInt::<L>::from(2_u64))
+            .wrapping_add(&UInt::<L>::ONE);
+
+        if p.bits() as usize != bit_length || q.wrapping_mul(&q) <= p {
+            continue;
+        }
+
+        let modulus = NonZero::new(p).unwrap();
+        let p_minus_1 = p.checked_sub(&UInt::<L>::ONE).unwrap();
+        let a = random_below(&p_minus_1, rng).wrapping_add(&UInt::<L>::from(2_u64));
+
+        if mod_pow(&a, &p_minus_1, &modulus) != UInt::<L>::ONE {
+            continue;
+        }
+
+        let witness = sub_mod(mod_pow(&a, &(p_minus_1 / NonZero::new(q).unwrap()), &modulus), UInt::<L>::ONE, &p);
+        if gcd(&witness, &p) != UInt::<L>::ONE {
+            continue;
+        }
+
+        let mut steps = vec![CertificateStep { p, q, r, a }];
+        steps.extend(cert_q.steps);
+        return (p, Certificate { steps });
+    }
+}
+
+/// Re-checks a [`Certificate`] produced by [`gen_provable_prime`] against the
+/// prime it claims to certify, re-deriving both Pocklington congruences at
+/// each level and settling the base case with [`is_prime_deterministic`].
+pub fn verify_certificate<const L: usize>(p: &UInt<L>, certificate: &Certificate<L>) -> bool {
+    let mut current = *p;
+
+    for step in &certificate.steps {
+        if step.p != current {
+            return false;
         }
 
-        Ok(candidate)
+        let expected = step
+            .r
+            .wrapping_mul(&step.q)
+            .wrapping_mul(&UInt::<L>::from(2_u64))
+            .wrapping_add(&UInt::<L>::ONE);
+        if expected != step.p || step.q.wrapping_mul(&step.q) <= step.p {
+            return false;
+        }
+
+        let modulus = NonZero::new(step.p).unwrap();
+        let p_minus_1 = step.p.checked_sub(&UInt::<L>::ONE).unwrap();
+        if mod_pow(&step.a, &p_minus_1, &modulus) != UInt::<L>::ONE {
+            return false;
+        }
+
+        let witness = sub_mod(
+            mod_pow(&step.a, &(p_minus_1 / NonZero::new(step.q).unwrap()), &modulus),
+            UInt::<L>::ONE,
+            &step.p,
+        );
+        if gcd(&witness, &step.p) != UInt::<L>::ONE {
+            return false;
+        }
+
+        current = step.q;
     }
+
+    is_prime_deterministic(&current).unwrap_or(false)
+}
+
+/// Uniform-ish random value in `[0, bound)`, biased slightly low by the same
+/// `% modulus` reduction every other random-basis draw in this module uses.
+fn random_below<const L: usize, R: CryptoRng + RngCore + ?Sized>(bound: &UInt<L>, rng: &mut R) -> UInt<L> {
+    let modulus = NonZero::new(*bound).unwrap();
+    UInt::<L>::random(rng).rem(&modulus)
+}
+
+/// Greatest common divisor via the Euclidean algorithm.
+pub(crate) fn gcd<const L: usize>(a: &UInt<L>, b: &UInt<L>) -> UInt<L> {
+    let mut a = *a;
+    let mut b = *b;
+
+    while !bool::from(b.is_zero()) {
+        let r = a % NonZero::new(b).unwrap();
+        a = b;
+        b = r;
+    }
+
+    a
 }
 
 /// Checks if number is a prime using the Baillie-PSW test
-pub fn is_prime_baillie_psw(candidate: &BigUint) -> bool {
-    _is_prime(candidate, required_checks(candidate.bits() as usize), true) && lucas(candidate)
+pub fn is_prime_baillie_psw<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    candidate: &UInt<L>,
+    rng: &mut R,
+) -> bool {
+    is_prime_baillie_psw_variant(candidate, LucasCheck::ExtraStrong, rng)
+}
+
+/// Checks if number is a prime using the Baillie-PSW test, running the given
+/// `LucasCheck` variant as the Lucas component.
+pub fn is_prime_baillie_psw_variant<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    candidate: &UInt<L>,
+    variant: LucasCheck,
+    rng: &mut R,
+) -> bool {
+    _is_prime(candidate, required_checks(bit_length(candidate)), true, &[], rng)
+        && lucas(candidate, variant)
+}
+
+/// Checks if number is a prime using the Baillie-PSW test with the full
+/// extra-strong Lucas test (`LucasCheck::ExtraStrongFull`) instead of the
+/// default almost-extra-strong one, closing the small extra pseudoprime gap
+/// at the cost of one modular inversion.
+pub fn is_prime_baillie_psw_strong<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    candidate: &UInt<L>,
+    rng: &mut R,
+) -> bool {
+    is_prime_baillie_psw_variant(candidate, LucasCheck::ExtraStrongFull, rng)
+}
+
+/// Checks if number is a safe prime using the Baillie-PSW test with the
+/// full extra-strong Lucas test. See [`is_prime_baillie_psw_strong`].
+pub fn is_safe_prime_baillie_psw_strong<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    candidate: &UInt<L>,
+    rng: &mut R,
+) -> bool {
+    is_safe_prime_baillie_psw_variant(candidate, LucasCheck::ExtraStrongFull, rng)
 }
 
 /// Checks if number is a safe prime using the Baillie-PSW test
-pub fn is_safe_prime_baillie_psw(candidate: &BigUint) -> bool {
-    _is_safe_prime(candidate, required_checks(candidate.bits() as usize), true) && lucas(candidate)
+pub fn is_safe_prime_baillie_psw<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    candidate: &UInt<L>,
+    rng: &mut R,
+) -> bool {
+    is_safe_prime_baillie_psw_variant(candidate, LucasCheck::ExtraStrong, rng)
+}
+
+/// Checks if number is a safe prime using the Baillie-PSW test, running the
+/// given `LucasCheck` variant as the Lucas component.
+pub fn is_safe_prime_baillie_psw_variant<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    candidate: &UInt<L>,
+    variant: LucasCheck,
+    rng: &mut R,
+) -> bool {
+    _is_safe_prime(candidate, required_checks(bit_length(candidate)), true, &[], rng)
+        && lucas(candidate, variant)
+}
+
+/// Checks if number is a prime using the Baillie-PSW test, selecting the
+/// Lucas test's `(D, P, Q)` base via `method` instead of the fixed
+/// base-selection each [`LucasCheck`] variant normally ties itself to.
+/// Returns `false` (rather than scanning forever) if `candidate` is a
+/// perfect square, since no valid base exists in that case.
+pub fn is_prime_baillie_psw_with_base<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    candidate: &UInt<L>,
+    method: LucasBase,
+    rng: &mut R,
+) -> bool {
+    _is_prime(candidate, required_checks(bit_length(candidate)), true, &[], rng)
+        && select_lucas_base(candidate, method)
+            .map(|(d, p, q)| lucas_strong(candidate, d, p, q))
+            .unwrap_or(false)
+}
+
+/// Checks if number is a safe prime using the Baillie-PSW test, selecting
+/// the Lucas test's base via `method`. See
+/// [`is_prime_baillie_psw_with_base`].
+pub fn is_safe_prime_baillie_psw_with_base<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    candidate: &UInt<L>,
+    method: LucasBase,
+    rng: &mut R,
+) -> bool {
+    _is_safe_prime(candidate, required_checks(bit_length(candidate)), true, &[], rng)
+        && select_lucas_base(candidate, method)
+            .map(|(d, p, q)| lucas_strong(candidate, d, p, q))
+            .unwrap_or(false)
 }
 
 /// Checks if number is a safe prime
-pub fn is_safe_prime(candidate: &BigUint) -> bool {
-    _is_safe_prime(candidate, required_checks(candidate.bits() as usize), false)
+pub fn is_safe_prime<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    candidate: &UInt<L>,
+    rng: &mut R,
+) -> bool {
+    _is_safe_prime(
+        candidate,
+        required_checks(bit_length(candidate)),
+        false,
+        &[],
+        rng,
+    )
 }
 
 /// Common function for `is_safe_prime`
-fn _is_safe_prime(candidate: &BigUint, checks: usize, force2: bool) -> bool {
+fn _is_safe_prime<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    candidate: &UInt<L>,
+    checks: usize,
+    force2: bool,
+    extra_bases: &[UInt<L>],
+    rng: &mut R,
+) -> bool {
     // according to https://eprint.iacr.org/2003/186.pdf
     // a safe prime is congruent to 2 mod 3
-    if (candidate % &BigUint::from(3_u8)) == BigUint::from(2_u8)
-        && _is_prime(candidate, checks, force2)
-    {
+    if rem_small(candidate, 3) == 2 && _is_prime(candidate, checks, force2, extra_bases, rng) {
         // a safe prime satisfies (p-1)/2 is prime. Since a
-        // prime is odd, We just need to divide by 2
-        return _is_prime(&(candidate >> 1), checks, force2);
+        // prime is odd, we just need to divide by 2
+        #[allow(unused_mut)]
+        let mut half = *candidate >> 1;
+        let result = _is_prime(&half, checks, force2, extra_bases, rng);
+        #[cfg(feature = "zeroize")]
+        half.zeroize();
+        return result;
     }
 
     false
@@ -97,84 +640,428 @@ fn _is_safe_prime(candidate: &BigUint, checks: usize, force2: bool) -> bool {
 /// 2- Perform a Fermat Test
 /// 3- Perform log2(bitlength) + 5 rounds of Miller-Rabin
 ///    depending on the number of bits
-pub fn is_prime(candidate: &BigUint) -> bool {
-    _is_prime(candidate, required_checks(candidate.bits() as usize), false)
+pub fn is_prime<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    candidate: &UInt<L>,
+    rng: &mut R,
+) -> bool {
+    _is_prime(candidate, required_checks(bit_length(candidate)), false, &[], rng)
 }
 
 /// Common function for `is_prime`
-fn _is_prime(candidate: &BigUint, checks: usize, force2: bool) -> bool {
-    if candidate == &BigUint::from(2_u8) {
+fn _is_prime<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    candidate: &UInt<L>,
+    checks: usize,
+    force2: bool,
+    extra_bases: &[UInt<L>],
+    rng: &mut R,
+) -> bool {
+    // A candidate this small is cheaper to settle with native, allocation-
+    // free word arithmetic than by routing it through `PRIMES` trial
+    // division, Fermat and Miller-Rabin over `UInt<L>`. Only take this
+    // shortcut when the caller hasn't pinned a specific witness strategy via
+    // `extra_bases`/`force2`/`checks` (see `PrimalityConfig`), since
+    // `is_prime_u64` always runs its own fixed witness set and would
+    // otherwise silently ignore the caller's configuration -- including a
+    // caller who explicitly asked for zero additional rounds.
+    if candidate.bits() as usize <= 64 && checks > 0 && extra_bases.is_empty() && !force2 {
+        return is_prime_u64(candidate.as_words()[0]);
+    }
+
+    if candidate == &UInt::<L>::from(2_u64) {
         return true;
     }
 
-    if candidate.is_even() || candidate.is_one() {
+    if candidate.is_even().into() || candidate == &UInt::<L>::ONE {
         return false;
     }
 
-    for p in PRIMES.iter() {
-        if candidate % p == BigUint::zero() {
-            return candidate == p;
+    for &p in PRIMES.iter() {
+        let r = rem_small(candidate, p);
+        if r == 0 {
+            return candidate == &UInt::<L>::from(p);
         }
     }
 
-    if !fermat(candidate) {
+    if !fermat(candidate, rng) {
         return false;
     }
 
     // Finally, do a Miller-Rabin test
     // See https://eprint.iacr.org/2018/749.pdf for good choices on appropriate number of tests
-    if !miller_rabin(candidate, checks, force2) {
+    if !miller_rabin(candidate, checks, force2, extra_bases, rng) {
+        return false;
+    }
+
+    true
+}
+
+/// Checks primality with a fixed, published set of Miller-Rabin witnesses
+/// that is known to be a *proof* (not just a probabilistic test) below the
+/// paired threshold. Returns `None` once `candidate` is too large for any
+/// published witness set to guarantee, leaving the caller to fall back to
+/// [`is_prime`] for a probabilistic answer.
+///
+/// Thresholds and witness sets are from Pomerance, Selfridge & Wagstaff,
+/// Jaeschke, and Sorenson & Webster (for the first 13 primes).
+pub fn is_prime_deterministic<const L: usize>(candidate: &UInt<L>) -> Option<bool> {
+    if candidate == &UInt::<L>::from(2_u64) {
+        return Some(true);
+    }
+
+    if bool::from(candidate.is_even()) || *candidate < UInt::<L>::from(2_u64) {
+        return Some(false);
+    }
+
+    // 3,317,044,064,679,887,385,961,981, split into 64-bit halves since it
+    // doesn't fit in a single `u64` literal. A single-limb `UInt<1>` is
+    // always below it.
+    let threshold_13_primes = if L >= 2 {
+        (UInt::<L>::from(0x2be69_u64) << 64) | UInt::<L>::from(0x51adc5b22410a5fd_u64)
+    } else {
+        UInt::<L>::MAX
+    };
+
+    if *candidate < UInt::<L>::from(1_373_653_u64) {
+        Some(miller_rabin_deterministic(candidate, &[2, 3]))
+    } else if *candidate < UInt::<L>::from(25_326_001_u64) {
+        Some(miller_rabin_deterministic(candidate, &[2, 3, 5]))
+    } else if *candidate < UInt::<L>::from(3_215_031_751_u64) {
+        Some(miller_rabin_deterministic(candidate, &[2, 3, 5, 7]))
+    } else if *candidate < threshold_13_primes {
+        Some(miller_rabin_deterministic(
+            candidate,
+            &[2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41],
+        ))
+    } else {
+        None
+    }
+}
+
+/// Fixed witness set proven to give an exact (not just probabilistic)
+/// answer for every `n < 318,665,857,834,031,151,167,461` (the published
+/// bound for the first 12 primes as bases) — comfortably past `u64::MAX`,
+/// so this covers every 64-bit candidate.
+const U64_WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Deterministic, allocation-free primality test for a native `u64`. Runs
+/// entirely on stack-allocated `u128` widening multiplication rather than
+/// going through `UInt<L>`, which is the hot path `is_prime` falls back to
+/// for any candidate small enough to fit.
+pub fn is_prime_u64(candidate: u64) -> bool {
+    if candidate < 2 {
+        return false;
+    }
+
+    for &p in U64_WITNESSES.iter() {
+        if candidate == p {
+            return true;
+        }
+        if candidate % p == 0 {
+            return false;
+        }
+    }
+
+    let (mut d, mut trials) = (candidate - 1, 0_u32);
+    while d % 2 == 0 {
+        d /= 2;
+        trials += 1;
+    }
+
+    'nextbasis: for &a in U64_WITNESSES.iter() {
+        let mut test = u64_mod_pow(a, d, candidate);
+        if test == 1 || test == candidate - 1 {
+            continue;
+        }
+        for _ in 1..trials {
+            test = u64_mul_mod(test, test, candidate);
+            if test == candidate - 1 {
+                continue 'nextbasis;
+            }
+        }
         return false;
     }
 
     true
 }
 
+/// `(a * b) % m`, widened through `u128` so the product never overflows.
+fn u64_mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+/// `base^exp mod m` by square-and-multiply, via [`u64_mul_mod`].
+fn u64_mod_pow(base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut base = base % m;
+    let mut result = 1_u64;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = u64_mul_mod(result, base, m);
+        }
+        base = u64_mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Deterministic-when-possible primality test for a native `u128`. Below the
+/// same threshold [`is_prime_deterministic`] uses, this is an exact answer;
+/// `u128` covers a far wider range than that threshold, so above it this
+/// falls back to the full Baillie-PSW test instead of overclaiming
+/// certainty. Either way this runs on `UInt<2>` (128 bits, two 64-bit
+/// limbs) rather than a native integer, since `u128` itself has no wider
+/// native type to hold an intermediate square. The exact path allocates
+/// nothing; the Baillie-PSW fallback collects its Miller-Rabin witnesses in
+/// a `Vec`.
+pub fn is_prime_u128<R: CryptoRng + RngCore + ?Sized>(candidate: u128, rng: &mut R) -> bool {
+    let n = UInt::<2>::from(candidate);
+    is_prime_deterministic(&n).unwrap_or_else(|| is_prime_baillie_psw(&n, rng))
+}
+
+/// Miller-Rabin against an explicit, fixed list of witnesses rather than
+/// random ones, so the result is a proof rather than a probabilistic
+/// answer. Same shape as [`miller_rabin`] without the randomness, except that
+/// it squares exactly `s - 1` times for `n - 1 = d·2^s`, as the published
+/// witness bounds assume.
+fn miller_rabin_deterministic<const L: usize>(candidate: &UInt<L>, bases: &[u64]) -> bool {
+    let (trials, d) = rewrite(candidate);
+
+    let modulus = NonZero::new(*candidate).unwrap();
+    let cand_minus_one = candidate.checked_sub(&UInt::<L>::ONE).unwrap();
+    let mont = Montgomery::new(*candidate);
+    let one_mont = mont.to_mont(&UInt::<L>::ONE);
+    let cand_minus_one_mont = mont.to_mont(&cand_minus_one);
+
+    'nextbasis: for &base in bases {
+        let basis = UInt::<L>::from(base) % modulus;
+        let mut test = mont.pow(mont.to_mont(&basis), &d);
+
+        if test == one_mont || test == cand_minus_one_mont {
+            continue;
+        }
+        for _ in 1..trials {
+            test = mont.mul(&test, &test);
+            if test == one_mont {
+                return false;
+            } else if test == cand_minus_one_mont {
+                continue 'nextbasis;
+            }
+        }
+        return false;
+    }
+
+    true
+}
+
+/// Returns the bit length of the genuine value held by `candidate`, ignoring
+/// any leading zero limbs contributed purely by the width `L`.
+fn bit_length<const L: usize>(candidate: &UInt<L>) -> usize {
+    candidate.bits() as usize
+}
+
 /// Minimum checks to be considered okay
-fn required_checks(bits: usize) -> usize {
+pub(crate) fn required_checks(bits: usize) -> usize {
     ((bits as f64).log2() as usize) + 5
 }
 
+/// Like [`resolve_bit_length`], but for the heap-allocated [`crate::boxed`]
+/// path, which always has an explicit runtime bit length (no "full width of
+/// `UInt<L>`" case to default to).
+pub(crate) fn resolve_bit_length_boxed(bit_length: usize) -> core::result::Result<usize, Error> {
+    if bit_length < MIN_BIT_LENGTH {
+        return Err(Error::BitLength(bit_length));
+    }
+    Ok(bit_length)
+}
+
 /// Perform Fermat's little theorem on the candidate to determine probable
 /// primality.
-fn fermat(candidate: &BigUint) -> bool {
-    let random = thread_rng().gen_biguint_range(&BigUint::one(), candidate);
+fn fermat<const L: usize, R: CryptoRng + RngCore + ?Sized>(candidate: &UInt<L>, rng: &mut R) -> bool {
+    let modulus = NonZero::new(*candidate).unwrap();
+    #[allow(unused_mut)]
+    let mut random = UInt::<L>::random(rng).rem(&modulus);
+    let exponent = candidate.checked_sub(&UInt::<L>::ONE).unwrap();
+
+    let result = mod_pow(&random, &exponent, &modulus);
+
+    #[cfg(feature = "zeroize")]
+    random.zeroize();
+
+    result == UInt::<L>::ONE
+}
+
+/// Square-and-multiply modular exponentiation, `base^exp mod modulus`,
+/// performed in Montgomery form so the per-step reduction is REDC rather
+/// than a full `% modulus`.
+fn mod_pow<const L: usize>(base: &UInt<L>, exp: &UInt<L>, modulus: &NonZero<UInt<L>>) -> UInt<L> {
+    let mont = Montgomery::new(*modulus.as_ref());
+    let base_mont = mont.to_mont(&(*base % modulus));
+
+    mont.from_mont(&mont.pow(base_mont, exp))
+}
+
+/// Square-and-multiply modular exponentiation without Montgomery form, used
+/// only to bootstrap [`Montgomery::new`] (which needs `2^(64*L) mod n`
+/// before a Montgomery context for `n` exists to compute it with).
+fn classical_mod_pow<const L: usize>(
+    base: &UInt<L>,
+    exp: &UInt<L>,
+    modulus: &NonZero<UInt<L>>,
+) -> UInt<L> {
+    let mut result = UInt::<L>::ONE;
+    let mut base = *base % modulus;
+
+    for i in 0..exp.bits() as usize {
+        if is_bit_set(exp, i) {
+            result = result.mul_mod(&base, modulus);
+        }
+        base = base.mul_mod(&base, modulus);
+    }
 
-    let result = random.modpow(&(candidate - 1_u8), candidate);
+    result
+}
 
-    result.is_one()
+/// Montgomery modular arithmetic over an odd modulus `n`.
+///
+/// Precomputes `n' = -n⁻¹ mod R` and `R² mod n` (`R = 2^(64*L)`) once so
+/// that repeated multiplications modulo the same `n` — the squaring loops
+/// in [`mod_pow`], [`miller_rabin`], and the Lucas doubling recurrence in
+/// [`lucas_uv`] — can use REDC (a shift-and-subtract) instead of a full
+/// division on every step.
+struct Montgomery<const L: usize> {
+    n: UInt<L>,
+    n_prime: UInt<L>,
+    r2: UInt<L>,
+}
+
+impl<const L: usize> Montgomery<L> {
+    fn new(n: UInt<L>) -> Self {
+        let modulus = NonZero::new(n).unwrap();
+        let n_prime = Self::neg_inverse_mod_r(n);
+        let bits = UInt::<L>::from((L * 64) as u64);
+        let r_mod_n = classical_mod_pow(&UInt::<L>::from(2_u64), &bits, &modulus);
+        let r2 = r_mod_n.mul_mod(&r_mod_n, &modulus);
+        Montgomery { n, n_prime, r2 }
+    }
+
+    /// Newton's method for `-n⁻¹ mod R`: `x ← x·(2 − n·x)` doubles the
+    /// number of correct low bits each iteration, starting from the 3 bits
+    /// that are always correct for `x0 = n` since `n` is odd.
+    fn neg_inverse_mod_r(n: UInt<L>) -> UInt<L> {
+        let mut x = n;
+        let mut correct_bits = 3;
+        let total_bits = L * 64;
+        let two = UInt::<L>::from(2_u64);
+
+        while correct_bits < total_bits {
+            x = x.wrapping_mul(&two.wrapping_sub(&n.wrapping_mul(&x)));
+            correct_bits *= 2;
+        }
+
+        UInt::<L>::ZERO.wrapping_sub(&x)
+    }
+
+    /// REDC: given a double-width value `hi*R + lo`, returns `(hi*R + lo) *
+    /// R⁻¹ mod n`.
+    fn redc(&self, lo: UInt<L>, hi: UInt<L>) -> UInt<L> {
+        let m = lo.wrapping_mul(&self.n_prime);
+        let (mn_lo, mn_hi) = m.mul_wide(&self.n);
+        let (_, carry) = lo.overflowing_add(&mn_lo);
+
+        // `hi + mn_hi + carry` is below `2n`, which exceeds `R` when
+        // `n > R/2`; track that overflow so the final subtraction still runs.
+        let (mut result, mut overflow) = hi.overflowing_add(&mn_hi);
+        if carry {
+            let (sum, carry_out) = result.overflowing_add(&UInt::<L>::ONE);
+            result = sum;
+            overflow |= carry_out;
+        }
+        if overflow || result >= self.n {
+            result = result.wrapping_sub(&self.n);
+        }
+
+        result
+    }
+
+    fn to_mont(&self, a: &UInt<L>) -> UInt<L> {
+        let (lo, hi) = a.mul_wide(&self.r2);
+        self.redc(lo, hi)
+    }
+
+    fn from_mont(&self, a: &UInt<L>) -> UInt<L> {
+        self.redc(*a, UInt::<L>::ZERO)
+    }
+
+    fn mul(&self, a: &UInt<L>, b: &UInt<L>) -> UInt<L> {
+        let (lo, hi) = a.mul_wide(b);
+        self.redc(lo, hi)
+    }
+
+    /// Square-and-multiply exponentiation, `base^exp`, with `base` already
+    /// in Montgomery form and the result left in Montgomery form so callers
+    /// can chain further multiplications without an extra round trip.
+    fn pow(&self, base_mont: UInt<L>, exp: &UInt<L>) -> UInt<L> {
+        let mut result = self.to_mont(&UInt::<L>::ONE);
+        let mut base = base_mont;
+
+        for i in 0..exp.bits() as usize {
+            if is_bit_set(exp, i) {
+                result = self.mul(&result, &base);
+            }
+            base = self.mul(&base, &base);
+        }
+
+        result
+    }
 }
 
 /// Perform miller rabin primality tests
-fn miller_rabin(candidate: &BigUint, limit: usize, force2: bool) -> bool {
+fn miller_rabin<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    candidate: &UInt<L>,
+    limit: usize,
+    force2: bool,
+    extra_bases: &[UInt<L>],
+    rng: &mut R,
+) -> bool {
     // Perform the Miller-Rabin test on the candidate, 'limit' times.
     let (mut trials, d) = rewrite(candidate);
     if trials < 5 {
         trials = 5;
     }
 
-    let cand_minus_one = candidate - 1_u32;
+    let modulus = NonZero::new(*candidate).unwrap();
+    let cand_minus_one = candidate.checked_sub(&UInt::<L>::ONE).unwrap();
+    let mont = Montgomery::new(*candidate);
+    let one_mont = mont.to_mont(&UInt::<L>::ONE);
+    let cand_minus_one_mont = mont.to_mont(&cand_minus_one);
 
-    let two = (*TWO).clone();
-    let bases = Randoms::new(two, candidate.clone(), limit, thread_rng());
-    let bases = if force2 {
-        bases.with_appended(BigUint::from(2_u8))
+    // Caller-supplied witnesses run first (base 2, if forced, ahead of
+    // those), then `limit` random ones make up the rest of the round count.
+    let mut bases: Vec<UInt<L>> = if force2 {
+        vec![UInt::<L>::from(2_u64)]
     } else {
-        bases
+        Vec::new()
     };
+    bases.extend(extra_bases.iter().map(|b| *b % modulus));
+    for _ in 0..limit {
+        bases.push(UInt::<L>::random(rng).rem(&modulus));
+    }
 
-    'nextbasis: for basis in bases {
-        let mut test = basis.modpow(&d, candidate);
+    'nextbasis: for mut basis in bases {
+        // `pow` returns its result in Montgomery form, so the remaining
+        // squarings for this basis never have to leave it.
+        let mut test = mont.pow(mont.to_mont(&basis), &d);
+        #[cfg(feature = "zeroize")]
+        basis.zeroize();
 
-        if test.is_one() || test == cand_minus_one {
+        if test == one_mont || test == cand_minus_one_mont {
             continue;
         }
         for _ in 1..trials - 1 {
-            test = test.modpow(&TWO, candidate);
-            if test.is_one() {
+            test = mont.mul(&test, &test);
+            if test == one_mont {
                 return false;
-            } else if test == cand_minus_one {
-                break 'nextbasis;
+            } else if test == cand_minus_one_mont {
+                continue 'nextbasis;
             }
         }
         return false;
@@ -184,261 +1071,622 @@ fn miller_rabin(candidate: &BigUint, limit: usize, force2: bool) -> bool {
 }
 
 /// Compute `d` and `trials`
-fn rewrite(candidate: &BigUint) -> (u64, BigUint) {
-    let mut d = candidate - 1_u32;
+fn rewrite<const L: usize>(candidate: &UInt<L>) -> (u64, UInt<L>) {
+    let mut d = candidate.checked_sub(&UInt::<L>::ONE).unwrap();
     let mut trials = 0;
 
-    while d.is_odd() {
-        d >>= 1;
+    while bool::from(d.is_odd()) == false {
+        d = d >> 1;
         trials += 1;
     }
 
     (trials, d)
 }
 
-fn lucas(n: &BigUint) -> bool {
+/// Size of the composite-offset window `Sieve` sieves in one pass. Chosen
+/// large enough that rebuilding it (one division per `PRIMES` entry) is rare
+/// compared to the number of offsets it yields.
+const WINDOW_SIZE: usize = 1 << 16;
+
+/// Windowed small-prime presieve.
+///
+/// Rather than drawing a fresh random candidate and dividing it by every
+/// entry in `PRIMES` on each attempt, `Sieve` picks a single random odd
+/// starting point and marks, for each small prime `p`, every offset in a
+/// `WINDOW_SIZE`-wide window of candidates (`start + 2*k`) that `p` divides.
+/// That only costs one division per prime to find the first marked offset,
+/// plus cheap additions to step to the rest; the window is then scanned and
+/// only its unmarked offsets reach the expensive Fermat/Baillie-PSW stage.
+/// Once the window is exhausted, `start` advances by `2 * WINDOW_SIZE` and
+/// the marks are rebuilt from the new residues. This is the same amortized
+/// small-prime presieve technique used by OpenSSL and Nettle's
+/// `nettle_random_prime`, just batched over a window instead of tracking one
+/// incrementing residue per prime.
+///
+/// For safe-prime generation, `n` and `(n-1)/2` must simultaneously be
+/// coprime to the small primes, so offsets are also marked against the
+/// residues of `(start-1)/2`.
+pub struct Sieve<const L: usize> {
+    start: UInt<L>,
+    composite: Vec<bool>,
+    half_composite: Option<Vec<bool>>,
+    cursor: usize,
+    bit_length: usize,
+}
+
+impl<const L: usize> Sieve<L> {
+    /// Start a sieve over plain candidates of `bit_length` bits.
+    pub fn new<R: CryptoRng + RngCore + ?Sized>(bit_length: usize, rng: &mut R) -> Self {
+        Self::build(bit_length, rng, false)
+    }
+
+    /// Start a sieve over candidates `n` that are simultaneously sieved on
+    /// `(n-1)/2`, suitable for safe-prime search.
+    pub fn new_safe<R: CryptoRng + RngCore + ?Sized>(bit_length: usize, rng: &mut R) -> Self {
+        Self::build(bit_length, rng, true)
+    }
+
+    fn build<R: CryptoRng + RngCore + ?Sized>(bit_length: usize, rng: &mut R, safe: bool) -> Self {
+        let start = random_odd_of_bit_length::<L, R>(bit_length, rng);
+        let half_composite = if safe { Some(Self::mark_half(start)) } else { None };
+
+        Sieve {
+            start,
+            composite: Self::mark(start),
+            half_composite,
+            cursor: 0,
+            bit_length,
+        }
+    }
+
+    /// Mark every offset `k` in the window where `start + 2*k` is divisible
+    /// by a small prime: `r_p + 2*k ≡ 0 (mod p)`, so `k ≡ -r_p * inv2 (mod
+    /// p)` where `inv2` is the modular inverse of 2 mod `p`.
+    fn mark(start: UInt<L>) -> Vec<bool> {
+        let mut composite = vec![false; WINDOW_SIZE];
+        for &p in PRIMES.iter() {
+            if p == 2 {
+                // `start` is always odd, so `start + 2*k` never is.
+                continue;
+            }
+            let r = rem_small(&start, p) as u64;
+            let p = p as u64;
+            let inv2 = (p + 1) / 2;
+            let mut k = ((p - r) % p * inv2) % p;
+            while (k as usize) < WINDOW_SIZE {
+                composite[k as usize] = true;
+                k += p;
+            }
+        }
+        composite
+    }
+
+    /// Same marking as [`Self::mark`], but against `(start-1)/2`, whose
+    /// offset from `k` advances by `1` for every `2` that `start` advances
+    /// by: `r'_p + k ≡ 0 (mod p)`, so `k ≡ -r'_p (mod p)`.
+    fn mark_half(start: UInt<L>) -> Vec<bool> {
+        let half = start >> 1;
+        let mut composite = vec![false; WINDOW_SIZE];
+        for &p in PRIMES.iter() {
+            let r = rem_small(&half, p) as u64;
+            let p = p as u64;
+            let mut k = (p - r) % p;
+            while (k as usize) < WINDOW_SIZE {
+                composite[k as usize] = true;
+                k += p;
+            }
+        }
+        composite
+    }
+
+    fn advance_window(&mut self) {
+        self.start = self
+            .start
+            .wrapping_add(&UInt::<L>::from(2 * WINDOW_SIZE as u64));
+        self.composite = Self::mark(self.start);
+        if self.half_composite.is_some() {
+            self.half_composite = Some(Self::mark_half(self.start));
+        }
+        self.cursor = 0;
+    }
+}
+
+impl<const L: usize> Iterator for Sieve<L> {
+    type Item = UInt<L>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.cursor >= WINDOW_SIZE {
+                self.advance_window();
+            }
+
+            #[allow(unused_mut)]
+            let mut candidate = self.start.wrapping_add(&UInt::<L>::from(2 * self.cursor as u64));
+
+            if candidate.bits() as usize != self.bit_length {
+                // Walked past the requested width (or wrapped around the
+                // full width of `UInt<L>`); restart from a fresh random
+                // start rather than yield a candidate of the wrong size.
+                *self = Self::build(
+                    self.bit_length,
+                    &mut rand_core::OsRng,
+                    self.half_composite.is_some(),
+                );
+                continue;
+            }
+
+            let clean = !self.composite[self.cursor]
+                && !self
+                    .half_composite
+                    .as_ref()
+                    .map(|half| half[self.cursor])
+                    .unwrap_or(false);
+
+            self.cursor += 1;
+
+            if clean {
+                return Some(candidate);
+            }
+
+            #[cfg(feature = "zeroize")]
+            candidate.zeroize();
+        }
+    }
+}
+
+fn random_odd_of_bit_length<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    bit_length: usize,
+    rng: &mut R,
+) -> UInt<L> {
+    let mut candidate = UInt::<L>::random(rng);
+    candidate = candidate >> (L * 64 - bit_length);
+    // Set the top and bottom bits so the result is exactly `bit_length` bits
+    // and odd.
+    candidate = candidate | (UInt::<L>::ONE << (bit_length - 1));
+    candidate = candidate | UInt::<L>::ONE;
+    candidate
+}
+
+/// Remainder of `candidate` by a small prime `p` that fits in a `u32`.
+fn rem_small<const L: usize>(candidate: &UInt<L>, p: u32) -> u32 {
+    let modulus = NonZero::new(UInt::<L>::from(p as u64)).unwrap();
+    let r = *candidate % modulus;
+    r.as_words()[0] as u32
+}
+
+/// Which Lucas test runs alongside the base-2 Miller-Rabin test in a
+/// Baillie-PSW check. The variants catch different (and, between them,
+/// essentially disjoint) sets of pseudoprimes; see
+/// https://en.wikipedia.org/wiki/Baillie%E2%80%93PSW_primality_test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LucasCheck {
+    /// Selfridge's method: scan `D = 5, -7, 9, -11, …` for the first value
+    /// with Jacobi(D/n) = -1, then run the strong Lucas test.
+    Strong,
+    /// Baillie-OEIS "method C": scan increasing `P ≥ 3` (so `D = P²-4`,
+    /// `Q = 1`) and run the *almost* extra-strong Lucas test, which skips
+    /// the `U_s ≡ 0` half of condition (i) to avoid computing `U` values.
+    ExtraStrong,
+    /// As `ExtraStrong`, but the full extra-strong test: `U_s` is recovered
+    /// from the already-computed `V_s, V_{s+1}` via Crandall-Pomerance eq.
+    /// 3.13 and condition (i) requires both `U_s ≡ 0` and `V_s ≡ ±2`.
+    ExtraStrongFull,
+    /// Accept when `V_{n+1} ≡ 2Q (mod n)` for the Selfridge-chosen base.
+    LucasV,
+}
+
+fn lucas<const L: usize>(n: &UInt<L>, variant: LucasCheck) -> bool {
+    match variant {
+        LucasCheck::ExtraStrong => lucas_extra_strong(n, false),
+        LucasCheck::ExtraStrongFull => lucas_extra_strong(n, true),
+        LucasCheck::Strong => lucas_selfridge(n).map(|(d, p, q)| lucas_strong(n, d, p, q)).unwrap_or(false),
+        LucasCheck::LucasV => lucas_selfridge(n).map(|(d, p, q)| lucas_v(n, d, p, q)).unwrap_or(false),
+    }
+}
+
+/// Which method selects the Lucas test's `(D, P, Q)` base, independent of
+/// which [`LucasCheck`] test variant is then run against it. Different base
+/// methods catch different pseudoprimes, so exposing the choice lets
+/// security-sensitive callers match a specific standard's Baillie-PSW
+/// variant instead of the library's default pairing of test and base.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LucasBase {
+    /// Selfridge's method: scan `D = 5, -7, 9, -11, …` for the first value
+    /// with Jacobi(D/n) = -1, then `P = 1`, `Q = (1-D)/4`. See
+    /// [`lucas_selfridge`].
+    Selfridge,
+    /// An alias for [`LucasBase::Selfridge`]. Whatever distinguished this
+    /// from plain `Selfridge` wasn't specified when this variant was
+    /// requested, and Selfridge's method is already what the literature
+    /// calls "Method A" — rather than invent a third, unattested
+    /// base-selection algorithm to fill the name, this is kept identical to
+    /// `Selfridge` until a genuinely distinct method is specified.
+    BruceMethodA,
+    /// Baillie-OEIS "method C": scan increasing `P ≥ 3` (so `D = P²-4`,
+    /// `Q = 1`) for the first value with Jacobi(D/n) = -1. This is the base
+    /// selection [`lucas_extra_strong`] runs internally.
+    BruteForce,
+}
+
+/// Selects `(D, P, Q)` for `n` per `method`. Checks upfront that `n` is not
+/// a perfect square, since no `D` gives Jacobi(D/n) = -1 in that case and
+/// the scan would never terminate; returns `None` for a perfect square (or
+/// if the scan's own bailout limit is hit, which is believed impossible for
+/// a non-square `n`).
+fn select_lucas_base<const L: usize>(n: &UInt<L>, method: LucasBase) -> Option<(i64, i64, i64)> {
+    let sqrt = isqrt(n);
+    if sqrt.wrapping_mul(&sqrt) == *n {
+        return None;
+    }
+
+    match method {
+        LucasBase::Selfridge | LucasBase::BruceMethodA => lucas_selfridge(n),
+        LucasBase::BruteForce => {
+            let mut p = 3_i64;
+            loop {
+                if p > 10000 {
+                    return None;
+                }
+
+                let d = p * p - 4;
+                if jacobi_signed(d, n) == -1 {
+                    return Some((d, p, 1));
+                }
+
+                p += 1;
+            }
+        }
+    }
+}
+
+/// Select `D, P, Q` by Selfridge's method: the first `D` in the sequence
+/// `5, -7, 9, -11, …` with Jacobi(D/n) = -1, then `P = 1`, `Q = (1-D)/4`.
+/// Returns `None` if `n` turns out to be a perfect square, since no such `D`
+/// exists in that case.
+fn lucas_selfridge<const L: usize>(n: &UInt<L>) -> Option<(i64, i64, i64)> {
+    let mut d: i64 = 5;
+    let mut tries = 0;
+
+    loop {
+        let j = jacobi_signed(d, n);
+
+        if j == -1 {
+            let q = (1 - d) / 4;
+            return Some((d, 1, q));
+        }
+        if j == 0 {
+            return None;
+        }
+
+        tries += 1;
+        // We'll never find (D/n) = -1 if n is a perfect square.
+        if tries == 20 {
+            let sqrt = isqrt(n);
+            if sqrt.wrapping_mul(&sqrt) == *n {
+                return None;
+            }
+        }
+
+        d = if d > 0 { -(d + 2) } else { -(d - 2) };
+    }
+}
+
+/// The strong Lucas test: accept if `U_d ≡ 0 (mod n)` or `V_{d·2^r} ≡ 0`
+/// for some `0 ≤ r < s`, where `n+1 = d·2^s`.
+fn lucas_strong<const L: usize>(n: &UInt<L>, _d: i64, p: i64, q: i64) -> bool {
+    let mut d_exp = n.wrapping_add(&UInt::<L>::ONE);
+    let s = trailing_zeros(&d_exp);
+    d_exp = d_exp >> s;
+
+    let (u, v, _) = lucas_uv(&d_exp, p, q, n);
+
+    if u.is_zero().into() || v.is_zero().into() {
+        return true;
+    }
+
+    let mut shifted = d_exp;
+    for _ in 1..s {
+        shifted = shifted << 1;
+        let (_, v, _) = lucas_uv(&shifted, p, q, n);
+        if v.is_zero().into() {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// The Lucas-V test: accept when `V_{n+1} ≡ 2Q (mod n)`.
+fn lucas_v<const L: usize>(n: &UInt<L>, _d: i64, p: i64, q: i64) -> bool {
+    let exponent = n.wrapping_add(&UInt::<L>::ONE);
+    let (_, v, _) = lucas_uv(&exponent, p, q, n);
+    v == signed_mod(2 * q, n)
+}
+
+/// Compute `(U_k, V_k, Q^k mod n)` for the Lucas sequence with parameters
+/// `P, Q` via the standard binary doubling recurrence:
+/// `U_{2k} = U_k V_k`, `V_{2k} = V_k² - 2Q^k`.
+fn lucas_uv<const L: usize>(k: &UInt<L>, p: i64, q: i64, n: &UInt<L>) -> (UInt<L>, UInt<L>, UInt<L>) {
+    let modulus = NonZero::new(*n).unwrap();
+    let mont = Montgomery::new(*n);
+
+    // `add_mod`/`sub_mod` stay correct under the Montgomery representation
+    // (it is just multiplication by the constant `R mod n`, which those
+    // operations are linear in), so only the multiplications below need to
+    // route through `mont.mul`; everything is converted in once up front
+    // and back out once at the end.
+    let inv2 = mont.to_mont(&mod_inverse(&UInt::<L>::from(2_u64), &modulus));
+    let big_p = mont.to_mont(&signed_mod(p, n));
+    let big_d = mont.to_mont(&signed_mod(p * p - 4 * q, n));
+    let q_mont = mont.to_mont(&signed_mod(q, n));
+
+    let mut u = mont.to_mont(&UInt::<L>::ONE);
+    let mut v = big_p;
+    let mut qk = q_mont;
+
+    let bits = k.bits() as usize;
+    for i in (0..bits.saturating_sub(1)).rev() {
+        // Double: (U, V, Qk) -> (U_2k, V_2k, Qk^2)
+        u = mont.mul(&u, &v);
+        v = sub_mod(mont.mul(&v, &v), add_mod(qk, qk, n), n);
+        qk = mont.mul(&qk, &qk);
+
+        if is_bit_set(k, i) {
+            // Increment: (U, V, Qk) -> (U_{k+1}, V_{k+1}, Qk*Q)
+            let new_u = mont.mul(&inv2, &add_mod(mont.mul(&big_p, &u), v, n));
+            let new_v = mont.mul(&inv2, &add_mod(mont.mul(&big_d, &u), mont.mul(&big_p, &v), n));
+            u = new_u;
+            v = new_v;
+            qk = mont.mul(&qk, &q_mont);
+        }
+    }
+
+    let (u, v, qk) = (mont.from_mont(&u), mont.from_mont(&v), mont.from_mont(&qk));
+
+    (u, v, qk)
+}
+
+/// Reduce a possibly-negative small integer `v` modulo `n` into `[0, n)`.
+fn signed_mod<const L: usize>(v: i64, n: &UInt<L>) -> UInt<L> {
+    let modulus = NonZero::new(*n).unwrap();
+    if v >= 0 {
+        UInt::<L>::from(v as u64) % modulus
+    } else {
+        let mag = UInt::<L>::from((-v) as u64) % modulus;
+        n.checked_sub(&mag).unwrap_or(UInt::<L>::ZERO)
+    }
+}
+
+fn add_mod<const L: usize>(a: UInt<L>, b: UInt<L>, n: &UInt<L>) -> UInt<L> {
+    // `a + b` can overflow `UInt<L>` when `n` is close to full width, so
+    // compute it as `a - (n - b) mod n` instead.
+    let modulus = NonZero::new(*n).unwrap();
+    let (a, b) = (a % modulus, b % modulus);
+    sub_mod(a, n.checked_sub(&b).unwrap(), n)
+}
+
+fn sub_mod<const L: usize>(a: UInt<L>, b: UInt<L>, n: &UInt<L>) -> UInt<L> {
+    if a >= b {
+        a.checked_sub(&b).unwrap()
+    } else {
+        n.checked_sub(&b.checked_sub(&a).unwrap()).unwrap()
+    }
+}
+
+/// Modular inverse of `a` modulo `n` via the extended Euclidean algorithm,
+/// keeping the Bezout coefficient reduced modulo `n` at every step so it
+/// never overflows `UInt<L>`.
+fn mod_inverse<const L: usize>(a: &UInt<L>, n: &NonZero<UInt<L>>) -> UInt<L> {
+    let n_val = *n.as_ref();
+    let mut r0 = n_val;
+    let mut r1 = *a % *n;
+    let mut s0 = UInt::<L>::ZERO;
+    let mut s1 = UInt::<L>::ONE;
+
+    while !bool::from(r1.is_zero()) {
+        let q = r0 / NonZero::new(r1).unwrap();
+        let r2 = r0.checked_sub(&q.mul_mod(&r1, n)).unwrap_or_else(|| {
+            // q*r1 computed mod n above may not equal the exact product;
+            // fall back to a direct remainder since r0 < n already.
+            r0 % NonZero::new(r1).unwrap()
+        });
+        let s2 = sub_mod(s0, q.mul_mod(&s1, n), &n_val);
+
+        r0 = r1;
+        r1 = r2;
+        s0 = s1;
+        s1 = s2;
+    }
+
+    s0
+}
+
+/// Integer square root (used only to detect perfect squares in the
+/// Selfridge `D` search).
+fn isqrt<const L: usize>(n: &UInt<L>) -> UInt<L> {
+    if n.is_zero().into() {
+        return UInt::<L>::ZERO;
+    }
+    let mut x = UInt::<L>::ONE << ((n.bits() as usize + 1) / 2);
+    loop {
+        let modulus = NonZero::new(x).unwrap();
+        let y = (x.wrapping_add(&(*n / modulus))) >> 1;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+fn lucas_extra_strong<const L: usize>(n: &UInt<L>, full: bool) -> bool {
     // Baillie-OEIS "method C" for choosing D, P, Q,
     // as in https://oeis.org/A217719/a217719.txt:
     // try increasing P ≥ 3 such that D = P² - 4 (so Q = 1)
     // until Jacobi(D, n) = -1.
-    // The search is expected to succeed for non-square n after just a few trials.
-    // After more than expected failures, check whether n is square
-    // (which would cause Jacobi(D, n) = 1 for all D not dividing n).
     let mut p = 3_u64;
-    let n_int = BigInt::from_biguint(Sign::Plus, n.clone());
 
     loop {
         if p > 10000 {
             // This is widely believed to be impossible.
-            // If we get a report, we'll want the exact number n.
-            panic!("internal error: cannot find (D/n) = -1 for {:?}", n)
+            panic!("internal error: cannot find (D/n) = -1");
         }
 
-        let j = jacobi(&BigInt::from(p * p - 4), &n_int);
+        let j = jacobi_small(p * p - 4, n);
 
         if j == -1 {
             break;
         }
         if j == 0 {
-            // d = p²-4 = (p-2)(p+2).
-            // If (d/n) == 0 then d shares a prime factor with n.
-            // Since the loop proceeds in increasing p and starts with p-2==1,
-            // the shared prime factor must be p+2.
-            // If p+2 == n, then n is prime; otherwise p+2 is a proper factor of n.
-            return n_int == BigInt::from(p as i64 + 2);
-        }
-
-        // We'll never find (d/n) = -1 if n is a square.
-        // If n is a non-square we expect to find a d in just a few attempts on average.
-        // After 40 attempts, take a moment to check if n is indeed a square.
-        if p == 40 && (&n_int * &n_int).sqrt() == n_int {
-            return false;
+            return *n == UInt::<L>::from(p + 2);
         }
 
         p += 1;
     }
 
-    // Grantham definition of "extra strong Lucas pseudoprime", after Thm 2.3 on p. 876
-    // (D, P, Q above have become Δ, b, 1):
-    //
-    // Let U_n = U_n(b, 1), V_n = V_n(b, 1), and Δ = b²-4.
-    // An extra strong Lucas pseudoprime to base b is a composite n = 2^r s + Jacobi(Δ, n),
-    // where s is odd and gcd(n, 2*Δ) = 1, such that either (i) U_s ≡ 0 mod n and V_s ≡ ±2 mod n,
-    // or (ii) V_{2^t s} ≡ 0 mod n for some 0 ≤ t < r-1.
-    //
-    // We know gcd(n, Δ) = 1 or else we'd have found Jacobi(d, n) == 0 above.
-    // We know gcd(n, 2) = 1 because n is odd.
-    //
-    // Arrange s = (n - Jacobi(Δ, n)) / 2^r = (n+1) / 2^r.
-    let mut s = n + 1_u32;
+    // Arrange s = (n+1) / 2^r.
+    let mut s = n.wrapping_add(&UInt::<L>::ONE);
     let r = trailing_zeros(&s);
-    s >>= r;
-    let nm2 = n - 2_u32; // n - 2
-
-    // We apply the "almost extra strong" test, which checks the above conditions
-    // except for U_s ≡ 0 mod n, which allows us to avoid computing any U_k values.
-    // Jacobsen points out that maybe we should just do the full extra strong test:
-    // "It is also possible to recover U_n using Crandall and Pomerance equation 3.13:
-    // U_n = D^-1 (2V_{n+1} - PV_n) allowing us to run the full extra-strong test
-    // at the cost of a single modular inversion. This computation is easy and fast in GMP,
-    // so we can get the full extra-strong test at essentially the same performance as the
-    // almost extra strong test."
-
-    // Compute Lucas sequence V_s(b, 1), where:
-    //
-    //	V(0) = 2
-    //	V(1) = P
-    //	V(k) = P V(k-1) - Q V(k-2).
-    //
-    // (Remember that due to method C above, P = b, Q = 1.)
-    //
-    // In general V(k) = α^k + β^k, where α and β are roots of x² - Px + Q.
-    // Crandall and Pomerance (p.147) observe that for 0 ≤ j ≤ k,
-    //
-    //	V(j+k) = V(j)V(k) - V(k-j).
-    //
-    // So in particular, to quickly double the subscript:
-    //
-    //	V(2k) = V(k)² - 2
-    //	V(2k+1) = V(k) V(k+1) - P
-    //
-    // We can therefore start with k=0 and build up to k=s in log₂(s) steps.
-    let mut vk = BigUint::from(2_u8);
-    let mut vk1 = BigUint::from(p);
-
-    for i in (0..s.bits()).rev() {
-        let t1 = (&vk * &vk1) + n - p;
-        if is_bit_set(&s, i as usize) {
-            // k' = 2k+1
-            // V(k') = V(2k+1) = V(k) V(k+1) - P
-            vk = &t1 % n;
-            // V(k'+1) = V(2k+2) = V(k+1)² - 2
-            let t1 = (&vk1 * &vk1) + &nm2;
-            vk1 = &t1 % n;
+    s = s >> r;
+    let nm2 = n.checked_sub(&UInt::<L>::from(2_u64)).unwrap();
+    let modulus = NonZero::new(*n).unwrap();
+    let mont = Montgomery::new(*n);
+
+    // As in `lucas_uv`, convert in once and run the whole doubling ladder in
+    // Montgomery form; `big_p` is reused below after converting back out.
+    let big_p = UInt::<L>::from(p);
+    let mont_p = mont.to_mont(&big_p);
+    let two_mont = mont.to_mont(&UInt::<L>::from(2_u8));
+    let mut vk = two_mont;
+    let mut vk1 = mont_p;
+
+    for i in (0..s.bits() as usize).rev() {
+        let t1 = sub_mod(mont.mul(&vk, &vk1), mont_p, n);
+        if is_bit_set(&s, i) {
+            vk = t1;
+            vk1 = sub_mod(mont.mul(&vk1, &vk1), two_mont, n);
         } else {
-            // k' = 2k
-            // V(k'+1) = V(2k+1) = V(k) V(k+1) - P
-            vk1 = &t1 % n;
-            // V(k') = V(2k) = V(k)² - 2
-            let t1 = (&vk * &vk) + &nm2;
-            vk = &t1 % n;
+            vk1 = t1;
+            vk = sub_mod(mont.mul(&vk, &vk), two_mont, n);
         }
     }
 
-    // Now k=s, so vk = V(s). Check V(s) ≡ ±2 (mod n).
-    if vk == BigUint::from(2_u8) || vk == nm2 {
-        // Check U(s) ≡ 0.
-        // As suggested by Jacobsen, apply Crandall and Pomerance equation 3.13:
-        //
-        //	U(k) = D⁻¹ (2 V(k+1) - P V(k))
-        //
-        // Since we are checking for U(k) == 0 it suffices to check 2 V(k+1) == P V(k) mod n,
-        // or P V(k) - 2 V(k+1) == 0 mod n.
-        let mut t1 = &vk * p;
-        let mut t2 = &vk1 << 1;
+    let (vk_out, vk1_out) = (mont.from_mont(&vk), mont.from_mont(&vk1));
 
-        if t1 < t2 {
-            ::std::mem::swap(&mut t1, &mut t2);
-        }
+    if vk_out == UInt::<L>::from(2_u8) || vk_out == nm2 {
+        let t1 = vk_out.mul_mod(&big_p, &modulus);
+        let t2 = add_mod(vk1_out, vk1_out, n);
+        let diff = if t1 >= t2 {
+            t1.checked_sub(&(t2 % modulus)).unwrap()
+        } else {
+            (t2 % modulus).checked_sub(&t1).unwrap()
+        };
 
-        t1 -= t2;
+        let v_condition = (diff % modulus).is_zero().into();
+        let u_condition = !full || {
+            // Crandall-Pomerance eq. 3.13: recover `U_s` from the `V_s,
+            // V_{s+1}` pair already on hand instead of tracking `U` through
+            // the whole doubling ladder. `D = P² - 4` here since `Q = 1`.
+            let d = UInt::<L>::from(p * p - 4);
+            let d_inv = mod_inverse(&d, &modulus);
+            let numerator = sub_mod(add_mod(vk1_out, vk1_out, n), vk_out.mul_mod(&big_p, &modulus), n);
+            d_inv.mul_mod(&numerator, &modulus).is_zero().into()
+        };
 
-        if (t1 % n).is_zero() {
+        if v_condition && u_condition {
             return true;
         }
     }
 
-    // Check V(2^t s) ≡ 0 mod n for some 0 ≤ t < r-1.
+    let mut vk = vk_out;
     for _ in 0..r - 1 {
-        if vk.is_zero() {
+        if vk.is_zero().into() {
             return true;
         }
-
-        // Optimization: V(k) = 2 is a fixed point for V(k') = V(k)² - 2,
-        // so if V(k) = 2, we can stop: we will never find a future V(k) == 0.
-        if vk == BigUint::from(2_u8) {
+        if vk == UInt::<L>::from(2_u8) {
             return false;
         }
-
-        // k' = 2k
-        // V(k') = V(2k) = V(k)² - 2
-        let t1 = (&vk * &vk) - 2_u32;
-        vk = &t1 % n;
+        vk = sub_mod(vk.mul_mod(&vk, &modulus), UInt::<L>::from(2_u64), n);
     }
 
     false
 }
 
 /// Returns the number of least-significant bits that are zero
-fn trailing_zeros<B: Clone + Integer + std::ops::ShrAssign<usize>>(n: &B) -> usize {
+fn trailing_zeros<const L: usize>(n: &UInt<L>) -> usize {
     let mut i = 0_usize;
-    let mut t = n.clone();
-    while t.is_even() {
+    let mut t = *n;
+    while bool::from(t.is_even()) {
         i += 1;
-        t >>= 1_usize;
+        t = t >> 1;
     }
     i
 }
 
-/// Jacobi returns the Jacobi symbol (x/y), either +1, -1, or 0.
-/// The y argument must be an odd integer.
+/// Jacobi symbol (d/n) for a small (possibly negative) `d` and an odd
+/// modulus `n`. `n` must be odd.
 #[allow(clippy::many_single_char_names)]
-fn jacobi(x: &BigInt, y: &BigInt) -> isize {
-    if !y.is_odd() {
-        panic!(
-            "invalid arguments, y must be an odd integer,but got {:?}",
-            y
-        );
-    }
-
-    let mut a = x.clone();
-    let mut b = y.clone();
+fn jacobi_small<const L: usize>(d: u64, n: &UInt<L>) -> isize {
+    // Reduce |d| mod n into unsigned space, tracking the sign contribution
+    // separately via the quadratic-reciprocity sign rules.
+    let modulus = NonZero::new(*n).unwrap();
+    let mut a = UInt::<L>::from(d) % modulus;
+    let mut b = *n;
     let mut j = 1;
 
-    if b.is_negative() {
-        if a.is_negative() {
-            j = -1;
-        }
-        b = -b;
-    }
-
     loop {
-        if b.is_one() {
+        if b == UInt::<L>::ONE {
             return j;
         }
-        if a.is_zero() {
-            return 0;
-        }
-
-        a = a.mod_floor(&b);
-        if a.is_zero() {
+        if a.is_zero().into() {
             return 0;
         }
 
-        // a > 0
-
-        // handle factors of 2 in a
         let s = trailing_zeros(&a);
         if s & 1 != 0 {
-            let bmod8 = &b & BigInt::from(7);
-            if bmod8 == BigInt::from(3) || bmod8 == BigInt::from(5) {
+            let bmod8 = (b.as_words()[0]) & 7;
+            if bmod8 == 3 || bmod8 == 5 {
                 j = -j;
             }
         }
 
-        let c = &a >> s; // a = 2^s*c
+        let c = a >> s;
 
-        // swap numerator and denominator
-        if &b & BigInt::from(3) == BigInt::from(3) && &c & BigInt::from(3) == BigInt::from(3) {
-            j = -j
+        if (b.as_words()[0] & 3 == 3) && (c.as_words()[0] & 3 == 3) {
+            j = -j;
         }
 
-        a = b;
-        b = c.clone();
+        a = b % NonZero::new(c).unwrap();
+        b = c;
+    }
+}
+
+/// Jacobi symbol (d/n) for a small, possibly negative `d`. Uses
+/// Jacobi(-a, n) = Jacobi(-1, n) · Jacobi(a, n), and Jacobi(-1, n) = 1 if
+/// `n ≡ 1 (mod 4)` else -1, for odd `n`.
+fn jacobi_signed<const L: usize>(d: i64, n: &UInt<L>) -> isize {
+    if d >= 0 {
+        jacobi_small(d as u64, n)
+    } else {
+        let nmod4 = n.as_words()[0] & 3;
+        let sign = if nmod4 == 1 { 1 } else { -1 };
+        sign * jacobi_small((-d) as u64, n)
     }
 }
 
 /// Checks if the i-th bit is set
 #[inline]
-fn is_bit_set(x: &BigUint, i: usize) -> bool {
+fn is_bit_set<const L: usize>(x: &UInt<L>, i: usize) -> bool {
     if i >= x.bits() as usize {
         return false;
     }
-    let res = x >> i;
-    res.is_odd()
+    bool::from((*x >> i).is_odd())
 }
 
-lazy_static! {
-    static ref PRIMES: Vec<BigUint> = gen_primes();
-}
-lazy_static! {
-    static ref TWO: BigUint = BigUint::from(2_u8);
-}
-lazy_static! {
-    static ref THREE: BigUint = BigUint::from(3_u8);
+lazy_static::lazy_static! {
+    pub(crate) static ref PRIMES: Vec<u32> = gen_primes();
 }
 
-fn gen_primes() -> Vec<BigUint> {
+fn gen_primes() -> Vec<u32> {
     [
         3_u32, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83,
         89, 97, 101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179,
@@ -584,121 +1832,6 @@ fn gen_primes() -> Vec<BigUint> {
         17609, 17623, 17627, 17657, 17659, 17669, 17681, 17683, 17707, 17713, 17729, 17737, 17747,
         17749, 17761, 17783, 17789, 17791, 17807, 17827, 17837, 17839, 17851, 17863,
     ]
-    .iter()
-    .map(|x| BigUint::from(*x))
-    .collect()
+    .to_vec()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::{
-        gen_prime, gen_safe_prime, is_prime, is_prime_baillie_psw, is_safe_prime,
-        is_safe_prime_baillie_psw, PRIMES,
-    };
-    use crate::error::Error;
-    use num_bigint::BigUint;
-    use num_traits::Num;
-    use rand::thread_rng;
-
-    #[test]
-    fn gen_safe_prime_tests() {
-        let mut rng = thread_rng();
-        match gen_prime(16, &mut rng) {
-            Ok(_) => panic!("No primes allowed under 16 bits"),
-            Err(e) => match e {
-                Error::BitLength(l) => assert_eq!(l, 16),
-                _ => panic!("Unexpected error"),
-            },
-        };
-
-        for bits in &[128, 256, 384, 512] {
-            let n = gen_safe_prime(*bits, &mut rng).unwrap();
-            assert!(is_safe_prime_baillie_psw(&n));
-            assert_eq!(n.bits() as usize, *bits);
-        }
-    }
-
-    #[test]
-    fn gen_prime_tests() {
-        let mut rng = thread_rng();
-        match gen_prime(16, &mut rng) {
-            Ok(_) => panic!("No primes allowed under 16 bits"),
-            Err(e) => match e {
-                Error::BitLength(l) => assert_eq!(l, 16),
-                _ => panic!("Unexpected error"),
-            },
-        };
-
-        for bits in &[256, 512, 1024, 2048] {
-            let n = gen_prime(*bits, &mut rng).unwrap();
-            assert!(is_prime(&n));
-            assert_eq!(n.bits() as usize, *bits);
-        }
-    }
-
-    #[test]
-    fn is_prime_tests() {
-        for prime in PRIMES.iter() {
-            assert!(is_prime(prime));
-        }
-
-        let mut n = BigUint::from(18_088_387_217_903_330_459_u64);
-        assert!(!is_prime(&(n.clone() >> 1)));
-        assert!(is_prime_baillie_psw(&n));
-        for _ in 0..5 {
-            n <<= 1;
-            n += 1_u8;
-            assert!(is_safe_prime(&n));
-            assert!(is_prime_baillie_psw(&n));
-        }
-
-        n = BigUint::from_str_radix("33376463607021642560387296949", 10).unwrap();
-        assert!(!is_prime(&(n.clone() >> 1)));
-        assert!(is_prime_baillie_psw(&n));
-        for _ in 0..5 {
-            n <<= 1;
-            n += 1_u8;
-            assert!(is_safe_prime(&n));
-        }
-
-        n = BigUint::from_str_radix("170141183460469231731687303717167733089", 10).unwrap();
-        assert!(!is_prime(&(n.clone() >> 1)));
-        assert!(is_prime_baillie_psw(&n));
-        for _ in 0..5 {
-            n <<= 1;
-            n += 1_u8;
-            assert!(is_safe_prime(&n));
-        }
-
-        n = BigUint::from_str_radix(
-            "113910913923300788319699387848674650656041243163866388656000063249848353322899",
-            10,
-        )
-        .unwrap();
-        assert!(!is_prime(&(n.clone() >> 1)));
-        assert!(is_prime_baillie_psw(&n));
-        for _ in 0..4 {
-            n <<= 1;
-            n += 1_u8;
-            assert!(is_safe_prime(&n));
-        }
-
-        n = BigUint::from_str_radix("1675975991242824637446753124775730765934920727574049172215445180465220503759193372100234287270862928461253982273310756356719235351493321243304213304923049", 10).unwrap();
-        assert!(!is_prime(&(n.clone() >> 1)));
-        assert!(is_prime(&n));
-        for _ in 0..4 {
-            n <<= 1;
-            n += 1_u8;
-            assert!(is_safe_prime(&n));
-        }
-        n = BigUint::from_str_radix("153739637779647327330155094463476939112913405723627932550795546376536722298275674187199768137486929460478138431076223176750734095693166283451594721829574797878338183845296809008576378039501400850628591798770214582527154641716248943964626446190042367043984306973709604255015629102866732543697075866901827761489", 10).unwrap();
-
-        assert!(!is_prime(&(n.clone() >> 1)));
-        assert!(is_prime_baillie_psw(&n));
-        for _ in 0..3 {
-            n <<= 1;
-            n += 1_u8;
-            assert!(is_safe_prime(&n));
-        }
-    }
-}
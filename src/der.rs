@@ -0,0 +1,123 @@
+//! Minimal DER encoder/decoder for the `INTEGER` and `SEQUENCE` shapes this
+//! crate needs to serialize primes and certificates, without pulling in a
+//! full ASN.1 crate.
+
+use crypto_bigint::UInt;
+
+/// Encodes a DER length in short or long form.
+pub(crate) fn encode_length(len: usize) -> Vec<u8> {
+    if len < 0x80 {
+        return vec![len as u8];
+    }
+
+    let mut bytes = Vec::new();
+    let mut n = len;
+    while n > 0 {
+        bytes.push((n & 0xff) as u8);
+        n >>= 8;
+    }
+    bytes.reverse();
+
+    let mut out = vec![0x80 | bytes.len() as u8];
+    out.extend(bytes);
+    out
+}
+
+/// Decodes a DER length, returning `(length, bytes consumed)`.
+fn decode_length(input: &[u8]) -> Option<(usize, usize)> {
+    let &first = input.first()?;
+    if first & 0x80 == 0 {
+        return Some((first as usize, 1));
+    }
+
+    let count = (first & 0x7f) as usize;
+    if count == 0 || input.len() < 1 + count {
+        return None;
+    }
+
+    let mut len = 0_usize;
+    for &b in &input[1..1 + count] {
+        len = (len << 8) | b as usize;
+    }
+    Some((len, 1 + count))
+}
+
+/// Encodes `value` as a big-endian minimal two's-complement DER `INTEGER`
+/// (leading `0x00` pad when the high bit of the magnitude is set).
+pub(crate) fn encode_integer<const L: usize>(value: &UInt<L>) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(L * 8);
+    for &word in value.as_words().iter().rev() {
+        bytes.extend_from_slice(&word.to_be_bytes());
+    }
+
+    let mut start = 0;
+    while start < bytes.len() - 1 && bytes[start] == 0 {
+        start += 1;
+    }
+    let mut content = bytes[start..].to_vec();
+    if content[0] & 0x80 != 0 {
+        content.insert(0, 0);
+    }
+
+    let mut out = vec![0x02];
+    out.extend(encode_length(content.len()));
+    out.extend(content);
+    out
+}
+
+/// Decodes a DER `INTEGER` from the start of `input`, returning the value
+/// and the number of bytes consumed. Returns `None` on malformed input or if
+/// the magnitude doesn't fit in `UInt<L>`.
+pub(crate) fn decode_integer<const L: usize>(input: &[u8]) -> Option<(UInt<L>, usize)> {
+    if input.first() != Some(&0x02) {
+        return None;
+    }
+    let (len, len_size) = decode_length(&input[1..])?;
+    let start = 1 + len_size;
+    let end = start.checked_add(len)?;
+    if end > input.len() || len == 0 {
+        return None;
+    }
+
+    let mut content = &input[start..end];
+    while content.len() > 1 && content[0] == 0 {
+        content = &content[1..];
+    }
+    if content.len() > L * 8 {
+        return None;
+    }
+
+    let mut value = UInt::<L>::ZERO;
+    for &byte in content {
+        value = (value << 8) | UInt::<L>::from(byte as u64);
+    }
+    Some((value, end))
+}
+
+/// Wraps the concatenation of already-encoded `items` in a DER `SEQUENCE`.
+pub(crate) fn encode_sequence(items: &[Vec<u8>]) -> Vec<u8> {
+    let mut content = Vec::new();
+    for item in items {
+        content.extend_from_slice(item);
+    }
+
+    let mut out = vec![0x30];
+    out.extend(encode_length(content.len()));
+    out.extend(content);
+    out
+}
+
+/// Decodes a DER `SEQUENCE` from the start of `input`, returning its content
+/// bytes (for the caller to parse further) and the total bytes consumed.
+pub(crate) fn decode_sequence(input: &[u8]) -> Option<(&[u8], usize)> {
+    if input.first() != Some(&0x30) {
+        return None;
+    }
+    let (len, len_size) = decode_length(&input[1..])?;
+    let start = 1 + len_size;
+    let end = start.checked_add(len)?;
+    if end > input.len() {
+        return None;
+    }
+    Some((&input[start..end], end))
+}
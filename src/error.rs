@@ -15,6 +15,9 @@ pub enum Error {
     OsRngInitialization(rand_core::Error),
     /// Handles when the bit sizes are too small
     BitLength(usize),
+    /// Handles when `prev_prime` is asked for a prime preceding a value
+    /// that has none (2 or less)
+    NoPrecedingPrime,
 }
 
 impl fmt::Display for Error {
@@ -28,6 +31,7 @@ impl fmt::Display for Error {
                 "The given bit length is too small; must be at least {}: {}",
                 MIN_BIT_LENGTH, length
             ),
+            Error::NoPrecedingPrime => write!(f, "There is no prime strictly less than 2"),
         }
     }
 }
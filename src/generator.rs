@@ -0,0 +1,169 @@
+//! A generic access trait for prime generation and checking.
+//!
+//! The `prime` and `safe_prime` modules expose the same shape of free
+//! functions duplicated for each concern. `PrimeGenerator` collects them
+//! behind one trait implemented for `UInt<L>`, so generic code (an RSA
+//! key-gen crate, for instance) can be written once over any integer width
+//! instead of hardcoding calls into a specific module.
+
+use crypto_bigint::{BoxedUint, UInt};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::common;
+use crate::error::{Error, Result};
+
+/// Generates and checks primes for a particular integer width `L`.
+pub trait PrimeGenerator<const L: usize>: Sized {
+    /// Generates a new prime number with a size of `bit_length` bits.
+    /// `None` generates the largest prime that fits `UInt<L>`.
+    fn random_prime<R: CryptoRng + RngCore + ?Sized>(
+        rng: &mut R,
+        bit_length: Option<usize>,
+    ) -> Result<L>;
+
+    /// Generates a new safe prime number with a size of `bit_length` bits.
+    /// `None` generates the largest safe prime that fits `UInt<L>`.
+    fn random_safe_prime<R: CryptoRng + RngCore + ?Sized>(
+        rng: &mut R,
+        bit_length: Option<usize>,
+    ) -> Result<L>;
+
+    /// Checks whether `self` is prime.
+    fn is_prime<R: CryptoRng + RngCore + ?Sized>(&self, rng: &mut R) -> bool;
+
+    /// Checks whether `self` is a safe prime.
+    fn is_safe_prime<R: CryptoRng + RngCore + ?Sized>(&self, rng: &mut R) -> bool;
+}
+
+impl<const L: usize> PrimeGenerator<L> for UInt<L> {
+    fn random_prime<R: CryptoRng + RngCore + ?Sized>(
+        rng: &mut R,
+        bit_length: Option<usize>,
+    ) -> Result<L> {
+        common::gen_prime(bit_length, rng)
+    }
+
+    fn random_safe_prime<R: CryptoRng + RngCore + ?Sized>(
+        rng: &mut R,
+        bit_length: Option<usize>,
+    ) -> Result<L> {
+        common::gen_safe_prime(bit_length, rng)
+    }
+
+    fn is_prime<R: CryptoRng + RngCore + ?Sized>(&self, rng: &mut R) -> bool {
+        common::is_prime(self, rng)
+    }
+
+    fn is_safe_prime<R: CryptoRng + RngCore + ?Sized>(&self, rng: &mut R) -> bool {
+        common::is_safe_prime(self, rng)
+    }
+}
+
+/// A candidate big integer that can check its own primality, abstracting
+/// over both the const-generic `UInt<L>` and the heap-allocated `BoxedUint`.
+///
+/// Unlike [`PrimeGenerator`], this carries no `L`, so code that only needs
+/// to *check* a candidate (not generate one) can be written once against
+/// either backend, e.g. validating an externally-supplied RSA/DH prime
+/// without caring how it was produced. For the stronger Baillie-PSW
+/// guarantee, see [`StrongPrimeCandidate`], which `BoxedUint` does not
+/// implement.
+pub trait PrimeCandidate: Sized {
+    /// Checks whether `self` is prime.
+    fn check<R: CryptoRng + RngCore + ?Sized>(&self, rng: &mut R) -> bool;
+}
+
+impl<const L: usize> PrimeCandidate for UInt<L> {
+    fn check<R: CryptoRng + RngCore + ?Sized>(&self, rng: &mut R) -> bool {
+        common::is_prime(self, rng)
+    }
+}
+
+impl PrimeCandidate for BoxedUint {
+    fn check<R: CryptoRng + RngCore + ?Sized>(&self, rng: &mut R) -> bool {
+        crate::boxed::is_prime(self, rng)
+    }
+}
+
+/// A [`PrimeCandidate`] that can also run the stronger Baillie-PSW test.
+///
+/// Only implemented for `UInt<L>`: the heap-allocated `BoxedUint` path has
+/// no Lucas test to run it with yet (see [`crate::boxed`]), so there is no
+/// `BoxedUint` impl rather than one that silently falls back to
+/// [`PrimeCandidate::check`] under a stronger-sounding name.
+pub trait StrongPrimeCandidate: PrimeCandidate {
+    /// Checks whether `self` is prime using the Baillie-PSW test.
+    fn strong_check<R: CryptoRng + RngCore + ?Sized>(&self, rng: &mut R) -> bool;
+}
+
+impl<const L: usize> StrongPrimeCandidate for UInt<L> {
+    fn strong_check<R: CryptoRng + RngCore + ?Sized>(&self, rng: &mut R) -> bool {
+        common::is_prime_baillie_psw(self, rng)
+    }
+}
+
+/// A [`PrimeCandidate`] that can also be generated at random, with its own
+/// notion of what a "bit length" request looks like (`Option<usize>` for
+/// `UInt<L>`, where `None` means "the full width"; a plain `usize` for
+/// `BoxedUint`, which has no fixed width to default to).
+pub trait GeneratePrime: PrimeCandidate {
+    /// The bit-length request type this backend accepts.
+    type BitLength;
+
+    /// Generates a new prime, sized per `bit_length`.
+    fn from_rng<R: CryptoRng + RngCore + ?Sized>(
+        bit_length: Self::BitLength,
+        rng: &mut R,
+    ) -> core::result::Result<Self, Error>;
+}
+
+impl<const L: usize> GeneratePrime for UInt<L> {
+    type BitLength = Option<usize>;
+
+    fn from_rng<R: CryptoRng + RngCore + ?Sized>(
+        bit_length: Self::BitLength,
+        rng: &mut R,
+    ) -> core::result::Result<Self, Error> {
+        common::gen_prime(bit_length, rng)
+    }
+}
+
+impl GeneratePrime for BoxedUint {
+    type BitLength = usize;
+
+    fn from_rng<R: CryptoRng + RngCore + ?Sized>(
+        bit_length: Self::BitLength,
+        rng: &mut R,
+    ) -> core::result::Result<Self, Error> {
+        crate::boxed::gen_prime(bit_length, rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GeneratePrime, PrimeCandidate, PrimeGenerator};
+    use crypto_bigint::{BoxedUint, UInt};
+    use rand_core::OsRng;
+
+    #[test]
+    fn generic_over_width() {
+        fn make_prime<const L: usize>() -> bool {
+            let n = UInt::<L>::random_prime(&mut OsRng::default(), Some(128)).unwrap();
+            n.is_prime(&mut OsRng::default())
+        }
+
+        assert!(make_prime::<2>());
+        assert!(make_prime::<4>());
+    }
+
+    #[test]
+    fn generic_over_backend() {
+        fn make_prime<T: GeneratePrime>(bit_length: T::BitLength) -> bool {
+            let n = T::from_rng(bit_length, &mut OsRng::default()).unwrap();
+            n.check(&mut OsRng::default())
+        }
+
+        assert!(make_prime::<UInt<2>>(Some(128)));
+        assert!(make_prime::<BoxedUint>(128));
+    }
+}
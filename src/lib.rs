@@ -16,12 +16,16 @@
 
 #[macro_use]
 extern crate lazy_static;
-extern crate num_bigint;
-extern crate num_traits;
-extern crate num_integer;
-extern crate rand;
+extern crate crypto_bigint;
+extern crate rand_core;
 
+mod boxed;
 mod common;
+#[cfg(feature = "der")]
+mod der;
 pub mod error;
+pub mod generator;
 pub mod prime;
+pub mod provable;
+pub mod rsa;
 pub mod safe_prime;
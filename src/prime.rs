@@ -2,41 +2,238 @@
 
 use crypto_bigint::UInt;
 use rand_core::OsRng;
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 pub use crate::common::{
-    gen_prime as from_rng, is_prime as check_with, is_prime_baillie_psw as strong_check_with,
+    gen_prime as from_rng, gen_prime_with as from_rng_with, is_prime as check_with,
+    is_prime_baillie_psw as strong_check_with, is_prime_baillie_psw_strong as strong_check_with_full,
+    is_prime_baillie_psw_variant as strong_check_with_variant,
+    is_prime_baillie_psw_with_base as strong_check_with_base, is_prime_deterministic,
+    is_prime_u64 as check_u64, is_prime_with as check_with_config,
+    next_prime as next_prime_from_rng, prev_prime as prev_prime_from_rng, LucasBase, LucasCheck,
+    PrimalityConfig,
 };
+use crate::common::{resolve_bit_length, Sieve};
 use crate::error::Result;
+use rand_core::{CryptoRng, RngCore};
 
-/// Constructs a new prime number with a size of `bit_length` bits.
+/// Constructs a new prime number with a size of `bit_length` bits. Passing
+/// `None` generates the largest prime that fits `UInt<L>`, i.e. exactly
+/// `UInt::<L>::BITS` bits.
 ///
 /// This will initialize an `OsRng` instance and call the
 /// `from_rng()` function.
 ///
-/// Note: the `bit_length` MUST be at least 128-bits.
-pub fn new<const L: usize>(bit_length: usize) -> Result<L> {
+/// Note: an explicit `bit_length` MUST be at least 128-bits.
+pub fn new<const L: usize>(bit_length: Option<usize>) -> Result<L> {
     let mut rng = OsRng::default();
     from_rng::<L, _>(bit_length, &mut rng)
 }
 
+/// Like [`new`], but runs Miller-Rabin with the rounds and bases given by
+/// `config` instead of the library's default choices.
+pub fn new_with<const L: usize>(
+    bit_length: Option<usize>,
+    config: &PrimalityConfig<L>,
+) -> Result<L> {
+    let mut rng = OsRng::default();
+    from_rng_with::<L, _>(bit_length, &mut rng, config)
+}
+
 /// Test if number is prime by
 ///
 /// 1- Trial division by first 2048 primes
 /// 2- Perform a Fermat Test
 /// 3- Perform log2(bitlength) + 5 rounds of Miller-Rabin
 ///    depending on the number of bits
+///
+/// Candidates that fit in 64 bits are settled directly by [`check_u64`]
+/// instead, skipping all of the above.
 pub fn check<const L: usize>(candidate: &UInt<L>) -> bool {
     check_with(candidate, &mut OsRng::default())
 }
 
+/// Checks if number is prime, running Miller-Rabin with the rounds and
+/// bases given by `config` instead of the library's default choices.
+pub fn check_config<const L: usize>(candidate: &UInt<L>, config: &PrimalityConfig<L>) -> bool {
+    check_with_config(candidate, config, &mut OsRng::default())
+}
+
 /// Checks if number is a prime using the Baillie-PSW test
 pub fn strong_check<const L: usize>(candidate: &UInt<L>) -> bool {
     strong_check_with(candidate, &mut OsRng::default())
 }
 
+/// Checks if number is a prime using the Baillie-PSW test, running the
+/// given `LucasCheck` variant as the Lucas component instead of the default
+/// extra-strong test.
+pub fn strong_check_variant<const L: usize>(candidate: &UInt<L>, variant: LucasCheck) -> bool {
+    strong_check_with_variant(candidate, variant, &mut OsRng::default())
+}
+
+/// Checks if number is a prime using the Baillie-PSW test with the full
+/// extra-strong Lucas test instead of the default almost-extra-strong one.
+pub fn strong_check_full<const L: usize>(candidate: &UInt<L>) -> bool {
+    strong_check_with_full(candidate, &mut OsRng::default())
+}
+
+/// Checks if number is a prime using the Baillie-PSW test, selecting the
+/// Lucas test's base via `method` instead of the default pairing each
+/// `LucasCheck` variant normally uses.
+pub fn strong_check_base<const L: usize>(candidate: &UInt<L>, method: LucasBase) -> bool {
+    strong_check_with_base(candidate, method, &mut OsRng::default())
+}
+
+/// Finds the smallest prime strictly greater than `candidate`.
+pub fn next_prime<const L: usize>(candidate: &UInt<L>) -> UInt<L> {
+    next_prime_from_rng(candidate, &mut OsRng::default())
+}
+
+/// Finds the largest prime strictly less than `candidate`. Errors if
+/// `candidate <= 2`, since there is no such prime.
+pub fn prev_prime<const L: usize>(candidate: &UInt<L>) -> Result<L> {
+    prev_prime_from_rng(candidate, &mut OsRng::default())
+}
+
+/// Primality check for a native `u128`: exact below the threshold of
+/// [`is_prime_deterministic`], a Baillie-PSW probable-prime test above it.
+/// See [`check_u64`] for the 64-bit equivalent.
+pub fn check_u128(candidate: u128) -> bool {
+    crate::common::is_prime_u128(candidate, &mut OsRng::default())
+}
+
+/// Constructs a new safe prime `p` (a prime such that `(p-1)/2` is also
+/// prime) with a size of `bit_length` bits. A thin convenience wrapper
+/// around [`crate::safe_prime::new`] for callers who'd rather not import a
+/// second module just to ask for this property.
+pub fn new_safe<const L: usize>(bit_length: Option<usize>) -> Result<L> {
+    crate::safe_prime::new::<L>(bit_length)
+}
+
+/// Checks if `candidate` is a safe prime. See [`crate::safe_prime::check`].
+pub fn check_safe<const L: usize>(candidate: &UInt<L>) -> bool {
+    crate::safe_prime::check(candidate)
+}
+
+/// Checks if `candidate` is a safe prime using the Baillie-PSW test. See
+/// [`crate::safe_prime::strong_check`].
+pub fn strong_check_safe<const L: usize>(candidate: &UInt<L>) -> bool {
+    crate::safe_prime::strong_check(candidate)
+}
+
+/// Lazily yields small-prime-sieved candidates of `bit_length` bits, without
+/// running the expensive Fermat/Miller-Rabin stage on them — the same
+/// candidates [`from_rng`] draws from internally, exposed directly for
+/// callers who want to pull several candidates (or primes, by filtering with
+/// [`check`]) without repeating the sieve setup for each one.
+///
+/// Note: this walks the same windowed presieve described on
+/// [`crate::common::Sieve`], which batches its small-prime marks over a
+/// window rather than updating one residue per prime on every single step;
+/// the two are equivalent in the candidates they produce; the window is just
+/// cheaper to maintain than tracking 2048 incrementing residues by hand.
+pub fn sieve_from_rng<const L: usize, R: CryptoRng + RngCore + ?Sized>(
+    bit_length: Option<usize>,
+    rng: &mut R,
+) -> core::result::Result<impl Iterator<Item = UInt<L>> + '_, crate::error::Error> {
+    let bit_length = resolve_bit_length::<L>(bit_length)?;
+    Ok(Sieve::new(bit_length, rng))
+}
+
+/// Encodes a prime as a DER `INTEGER`: big-endian minimal two's-complement,
+/// with a leading `0x00` pad when the high bit of the magnitude is set.
+#[cfg(feature = "der")]
+pub fn to_der<const L: usize>(candidate: &UInt<L>) -> Vec<u8> {
+    crate::der::encode_integer(candidate)
+}
+
+/// Decodes a prime from the DER `INTEGER` encoding produced by [`to_der`].
+/// Returns `None` on malformed input or if the value doesn't fit in
+/// `UInt<L>`; does not itself check primality.
+#[cfg(feature = "der")]
+pub fn from_der<const L: usize>(bytes: &[u8]) -> Option<UInt<L>> {
+    crate::der::decode_integer(bytes).map(|(value, _)| value)
+}
+
+/// Constructs a new prime number of `bit_length` bits, heap-allocated at
+/// exactly that width instead of a fixed `UInt<L>`. For callers who choose
+/// their modulus size at runtime rather than compile time.
+pub fn new_boxed(bit_length: usize) -> core::result::Result<crypto_bigint::BoxedUint, crate::error::Error> {
+    crate::boxed::gen_prime(bit_length, &mut OsRng::default())
+}
+
+/// Checks if a heap-allocated number is prime.
+///
+/// There is no `strong_check_boxed` counterpart: the boxed path has no Lucas
+/// test to run (see [`crate::boxed`]), so a Baillie-PSW-strength check isn't
+/// available for this backend yet. This is the only entry point — it offers
+/// the same trial-division/Fermat/Miller-Rabin guarantee as [`check`], not
+/// the stronger one [`strong_check`] gives for `UInt<L>`.
+pub fn check_boxed(candidate: &crypto_bigint::BoxedUint) -> bool {
+    crate::boxed::is_prime(candidate, &mut OsRng::default())
+}
+
+/// A prime number that is wiped from memory when dropped.
+///
+/// Prime material generated for RSA/DH key construction should not linger
+/// in memory after the caller is done with it; this avoids having to
+/// remember to zero it out by hand.
+#[cfg(feature = "zeroize")]
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct Prime<const L: usize>(UInt<L>);
+
+#[cfg(feature = "zeroize")]
+impl<const L: usize> Prime<L> {
+    /// Unwraps the prime, bypassing the wipe-on-drop behavior.
+    pub fn into_inner(self) -> UInt<L> {
+        let inner = self.0;
+        core::mem::forget(self);
+        inner
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<const L: usize> core::ops::Deref for Prime<L> {
+    type Target = UInt<L>;
+
+    fn deref(&self) -> &UInt<L> {
+        &self.0
+    }
+}
+
+#[cfg(all(feature = "zeroize", feature = "der"))]
+impl<const L: usize> Prime<L> {
+    /// Encodes the prime as a DER `INTEGER`. See [`to_der`].
+    pub fn to_der(&self) -> Vec<u8> {
+        to_der(&self.0)
+    }
+
+    /// Decodes a prime from the DER `INTEGER` encoding produced by
+    /// [`Prime::to_der`]. Does not itself check primality.
+    pub fn from_der(bytes: &[u8]) -> Option<Self> {
+        from_der(bytes).map(Prime)
+    }
+}
+
+/// Like [`new`], but wraps the result in [`Prime`] so it is wiped from
+/// memory when dropped, unless explicitly extracted with
+/// [`Prime::into_inner`].
+#[cfg(feature = "zeroize")]
+pub fn gen_prime_zeroizing<const L: usize>(
+    bit_length: Option<usize>,
+) -> core::result::Result<Prime<L>, crate::error::Error> {
+    new::<L>(bit_length).map(Prime)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{check, new, strong_check};
+    use super::{
+        check, check_config, check_safe, new, new_safe, sieve_from_rng, strong_check,
+        strong_check_base, strong_check_safe, LucasBase, PrimalityConfig,
+    };
+    use crypto_bigint::{CheckedSub, UInt};
+    use rand_core::OsRng;
 
     #[test]
     fn tests() {
@@ -47,8 +244,70 @@ mod tests {
     }
 
     fn tests_impl<const L: usize>(bit_length: usize) {
-        let n = new::<L>(bit_length).unwrap();
+        let n = new::<L>(Some(bit_length)).unwrap();
         assert!(check(&n));
         assert!(strong_check(&n));
     }
+
+    #[test]
+    fn sieve_yields_eventual_prime() {
+        let n: UInt<2> = sieve_from_rng(Some(128), &mut OsRng::default())
+            .unwrap()
+            .find(|candidate| check(candidate))
+            .unwrap();
+        assert!(check(&n));
+    }
+
+    #[test]
+    fn deterministic_config_pins_exact_witnesses() {
+        let n = new::<2>(Some(128)).unwrap();
+        let config = PrimalityConfig::deterministic(vec![
+            UInt::<2>::from(2_u64),
+            UInt::<2>::from(3_u64),
+            UInt::<2>::from(5_u64),
+        ]);
+        assert!(check_config(&n, &config));
+    }
+
+    #[test]
+    fn strong_check_accepts_every_lucas_base() {
+        let n = new::<2>(Some(128)).unwrap();
+        assert!(strong_check_base(&n, LucasBase::Selfridge));
+        assert!(strong_check_base(&n, LucasBase::BruceMethodA));
+        assert!(strong_check_base(&n, LucasBase::BruteForce));
+    }
+
+    #[test]
+    fn safe_prime_convenience_wrappers() {
+        let n = new_safe::<2>(Some(128)).unwrap();
+        assert!(check_safe(&n));
+        assert!(strong_check_safe(&n));
+    }
+
+    #[test]
+    fn full_width_mode() {
+        let n = new::<4>(None).unwrap();
+        assert_eq!(n.bits() as usize, UInt::<4>::BITS as usize);
+        assert!(check(&n));
+    }
+
+    /// Regression test for candidates whose true bit length is much shorter
+    /// than the width `L` they're stored in: a genuine 127-bit prime held
+    /// in a `U512` has 385 leading zero bits, and the primality tests must
+    /// key off the actual magnitude rather than `L*64`.
+    #[test]
+    fn leading_zero_bits_are_handled() {
+        // The Mersenne prime 2^127 - 1, stored in a width far wider than
+        // its true bit length.
+        let mersenne_127 = (UInt::<8>::ONE << 127)
+            .checked_sub(&UInt::<8>::ONE)
+            .unwrap();
+        assert!(check(&mersenne_127));
+        assert!(strong_check(&mersenne_127));
+
+        // A composite with the same shape (many leading zero bits) must
+        // still be correctly rejected.
+        let composite = mersenne_127 - UInt::<8>::from(2_u64);
+        assert!(!check(&composite));
+    }
 }
@@ -0,0 +1,89 @@
+//! Generates primes with a Pocklington/Maurer certificate instead of relying
+//! on a probabilistic test.
+
+use crypto_bigint::UInt;
+use rand_core::OsRng;
+
+pub use crate::common::{
+    gen_provable_prime as from_rng, verify_certificate, Certificate, CertificateStep,
+};
+use crate::common::resolve_bit_length;
+use crate::error::Error;
+
+/// Constructs a new prime number of `bit_length` bits together with a
+/// certificate proving its primality, per [Maurer's algorithm][1].
+///
+/// This will initialize an `OsRng` instance and call the `from_rng()`
+/// function.
+///
+/// Note: `bit_length` MUST be at least 128-bits.
+///
+/// [1]: https://doi.org/10.1007/BF00202269
+pub fn new<const L: usize>(bit_length: usize) -> Result<(UInt<L>, Certificate<L>), Error> {
+    let bit_length = resolve_bit_length::<L>(Some(bit_length))?;
+
+    let mut rng = OsRng::default();
+    Ok(from_rng::<L, _>(bit_length, &mut rng))
+}
+
+/// Encodes a certificate as a DER `SEQUENCE` of per-level `SEQUENCE`s, each
+/// holding the `(p, q, r, a)` `INTEGER` tuple for that level, outermost
+/// (top-level prime) first.
+#[cfg(feature = "der")]
+pub fn to_der<const L: usize>(certificate: &Certificate<L>) -> Vec<u8> {
+    let steps = certificate
+        .steps
+        .iter()
+        .map(|step| {
+            crate::der::encode_sequence(&[
+                crate::der::encode_integer(&step.p),
+                crate::der::encode_integer(&step.q),
+                crate::der::encode_integer(&step.r),
+                crate::der::encode_integer(&step.a),
+            ])
+        })
+        .collect::<Vec<_>>();
+    crate::der::encode_sequence(&steps)
+}
+
+/// Decodes a certificate from the DER encoding produced by [`to_der`].
+/// Returns `None` on malformed input; does not itself verify the
+/// certificate, see [`verify_certificate`] for that.
+#[cfg(feature = "der")]
+pub fn from_der<const L: usize>(bytes: &[u8]) -> Option<Certificate<L>> {
+    let (mut content, _) = crate::der::decode_sequence(bytes)?;
+    let mut steps = Vec::new();
+
+    while !content.is_empty() {
+        let (mut step_content, step_len) = crate::der::decode_sequence(content)?;
+
+        let (p, consumed) = crate::der::decode_integer(step_content)?;
+        step_content = &step_content[consumed..];
+        let (q, consumed) = crate::der::decode_integer(step_content)?;
+        step_content = &step_content[consumed..];
+        let (r, consumed) = crate::der::decode_integer(step_content)?;
+        step_content = &step_content[consumed..];
+        let (a, _) = crate::der::decode_integer(step_content)?;
+
+        steps.push(CertificateStep { p, q, r, a });
+        content = &content[step_len..];
+    }
+
+    Some(Certificate { steps })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{new, verify_certificate};
+
+    #[test]
+    fn tests() {
+        tests_impl::<2>(128);
+        tests_impl::<4>(256);
+    }
+
+    fn tests_impl<const L: usize>(bit_length: usize) {
+        let (p, certificate) = new::<L>(bit_length).unwrap();
+        assert!(verify_certificate(&p, &certificate));
+    }
+}
@@ -0,0 +1,59 @@
+//! Generates prime pairs suitable for constructing RSA keys.
+
+use crypto_bigint::UInt;
+use rand_core::OsRng;
+
+pub use crate::common::gen_rsa_primes as from_rng;
+use crate::error::Error;
+
+/// Generates an RSA prime pair `(p, q)` for a `modulus_bits`-bit modulus
+/// with public exponent `public_exponent`. See [`from_rng`] for the
+/// invariants the pair satisfies.
+///
+/// This will initialize an `OsRng` instance and call the `from_rng()`
+/// function.
+pub fn new<const L: usize>(
+    modulus_bits: usize,
+    public_exponent: &UInt<L>,
+) -> Result<(UInt<L>, UInt<L>), Error> {
+    let mut rng = OsRng::default();
+    from_rng(modulus_bits, public_exponent, &mut rng)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::new;
+    use crate::common::gcd;
+    use crate::error::Error;
+    use crate::prime;
+    use crypto_bigint::{CheckedSub, UInt};
+
+    #[test]
+    fn tests() {
+        tests_impl::<4>(512);
+    }
+
+    fn tests_impl<const L: usize>(modulus_bits: usize) {
+        let e = UInt::<L>::from(65_537_u64);
+        let (p, q) = new::<L>(modulus_bits, &e).unwrap();
+
+        assert_ne!(p, q);
+        assert!(prime::check(&p));
+        assert!(prime::check(&q));
+        assert_eq!(gcd(&(p - UInt::<L>::ONE), &e), UInt::<L>::ONE);
+        assert_eq!(gcd(&(q - UInt::<L>::ONE), &e), UInt::<L>::ONE);
+
+        let distance = if p >= q {
+            p.checked_sub(&q).unwrap()
+        } else {
+            q.checked_sub(&p).unwrap()
+        };
+        assert!(distance > UInt::<L>::ONE << (modulus_bits / 2 - 100));
+    }
+
+    #[test]
+    fn rejects_modulus_too_small_for_the_distance_bound() {
+        let e = UInt::<2>::from(65_537_u64);
+        assert!(matches!(new::<2>(150, &e), Err(Error::BitLength(150))));
+    }
+}
@@ -2,37 +2,152 @@
 
 use crypto_bigint::UInt;
 use rand_core::OsRng;
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 pub use crate::common::{
-    gen_safe_prime as from_rng, is_safe_prime as check_with,
+    gen_safe_prime as from_rng, gen_safe_prime_with as from_rng_with, is_safe_prime as check_with,
     is_safe_prime_baillie_psw as strong_check_with,
+    is_safe_prime_baillie_psw_strong as strong_check_with_full,
+    is_safe_prime_baillie_psw_variant as strong_check_with_variant,
+    is_safe_prime_baillie_psw_with_base as strong_check_with_base,
+    is_safe_prime_with as check_with_config, next_safe_prime as next_safe_prime_from_rng,
+    LucasBase, LucasCheck, PrimalityConfig,
 };
 use crate::error::Result;
 
 /// Constructs a new safe prime number with a size of `bit_length` bits.
+/// Passing `None` generates the largest safe prime that fits `UInt<L>`,
+/// i.e. exactly `UInt::<L>::BITS` bits.
 ///
 /// This will initialize an `OsRng` instance and call the
 /// `from_rng()` function.
 ///
-/// Note: the `bit_length` MUST be at least 128-bits.
-pub fn new<const L: usize>(bit_length: usize) -> Result<L> {
+/// Note: an explicit `bit_length` MUST be at least 128-bits.
+pub fn new<const L: usize>(bit_length: Option<usize>) -> Result<L> {
     let mut rng = OsRng::default();
     from_rng::<L, _>(bit_length, &mut rng)
 }
 
+/// Like [`new`], but runs Miller-Rabin with the rounds and bases given by
+/// `config` instead of the library's default choices.
+pub fn new_with<const L: usize>(
+    bit_length: Option<usize>,
+    config: &PrimalityConfig<L>,
+) -> Result<L> {
+    let mut rng = OsRng::default();
+    from_rng_with::<L, _>(bit_length, &mut rng, config)
+}
+
 /// Checks if number is a safe prime
 pub fn check<const L: usize>(candidate: &UInt<L>) -> bool {
     check_with(candidate, &mut OsRng::default())
 }
 
+/// Checks if number is a safe prime, running Miller-Rabin with the rounds
+/// and bases given by `config` instead of the library's default choices.
+pub fn check_config<const L: usize>(candidate: &UInt<L>, config: &PrimalityConfig<L>) -> bool {
+    check_with_config(candidate, config, &mut OsRng::default())
+}
+
 /// Checks if number is a safe prime using the Baillie-PSW test
 pub fn strong_check<const L: usize>(candidate: &UInt<L>) -> bool {
     strong_check_with(candidate, &mut OsRng::default())
 }
 
+/// Checks if number is a safe prime using the Baillie-PSW test, running the
+/// given `LucasCheck` variant as the Lucas component instead of the default
+/// extra-strong test.
+pub fn strong_check_variant<const L: usize>(candidate: &UInt<L>, variant: LucasCheck) -> bool {
+    strong_check_with_variant(candidate, variant, &mut OsRng::default())
+}
+
+/// Checks if number is a safe prime using the Baillie-PSW test with the
+/// full extra-strong Lucas test instead of the default almost-extra-strong
+/// one.
+pub fn strong_check_full<const L: usize>(candidate: &UInt<L>) -> bool {
+    strong_check_with_full(candidate, &mut OsRng::default())
+}
+
+/// Checks if number is a safe prime using the Baillie-PSW test, selecting
+/// the Lucas test's base via `method` instead of the default pairing each
+/// `LucasCheck` variant normally uses.
+pub fn strong_check_base<const L: usize>(candidate: &UInt<L>, method: LucasBase) -> bool {
+    strong_check_with_base(candidate, method, &mut OsRng::default())
+}
+
+/// Finds the smallest safe prime strictly greater than `candidate`.
+pub fn next_safe_prime<const L: usize>(candidate: &UInt<L>) -> UInt<L> {
+    next_safe_prime_from_rng(candidate, &mut OsRng::default())
+}
+
+/// Encodes a safe prime as a DER `INTEGER`. See [`crate::prime::to_der`].
+#[cfg(feature = "der")]
+pub fn to_der<const L: usize>(candidate: &UInt<L>) -> Vec<u8> {
+    crate::der::encode_integer(candidate)
+}
+
+/// Decodes a safe prime from the DER `INTEGER` encoding produced by
+/// [`to_der`]. Does not itself check primality.
+#[cfg(feature = "der")]
+pub fn from_der<const L: usize>(bytes: &[u8]) -> Option<UInt<L>> {
+    crate::der::decode_integer(bytes).map(|(value, _)| value)
+}
+
+/// A safe prime number that is wiped from memory when dropped.
+///
+/// Safe primes are commonly used directly as DH/Paillier moduli, so leaking
+/// one leaks the whole group.
+#[cfg(feature = "zeroize")]
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct SafePrime<const L: usize>(UInt<L>);
+
+#[cfg(feature = "zeroize")]
+impl<const L: usize> SafePrime<L> {
+    /// Unwraps the safe prime, bypassing the wipe-on-drop behavior.
+    pub fn into_inner(self) -> UInt<L> {
+        let inner = self.0;
+        core::mem::forget(self);
+        inner
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<const L: usize> core::ops::Deref for SafePrime<L> {
+    type Target = UInt<L>;
+
+    fn deref(&self) -> &UInt<L> {
+        &self.0
+    }
+}
+
+#[cfg(all(feature = "zeroize", feature = "der"))]
+impl<const L: usize> SafePrime<L> {
+    /// Encodes the safe prime as a DER `INTEGER`. See [`to_der`].
+    pub fn to_der(&self) -> Vec<u8> {
+        to_der(&self.0)
+    }
+
+    /// Decodes a safe prime from the DER `INTEGER` encoding produced by
+    /// [`SafePrime::to_der`]. Does not itself check primality.
+    pub fn from_der(bytes: &[u8]) -> Option<Self> {
+        from_der(bytes).map(SafePrime)
+    }
+}
+
+/// Like [`new`], but wraps the result in [`SafePrime`] so it is wiped from
+/// memory when dropped, unless explicitly extracted with
+/// [`SafePrime::into_inner`].
+#[cfg(feature = "zeroize")]
+pub fn gen_safe_prime_zeroizing<const L: usize>(
+    bit_length: Option<usize>,
+) -> core::result::Result<SafePrime<L>, crate::error::Error> {
+    new::<L>(bit_length).map(SafePrime)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{check, new, strong_check};
+    use super::{check, new, strong_check, strong_check_base, LucasBase};
 
     #[test]
     fn tests() {
@@ -42,7 +157,22 @@ mod tests {
     }
 
     fn tests_impl<const L: usize>(bit_length: usize) {
-        let n = new::<L>(bit_length).unwrap();
+        let n = new::<L>(Some(bit_length)).unwrap();
+        assert!(check(&n));
+        assert!(strong_check(&n));
+    }
+
+    #[test]
+    fn strong_check_accepts_every_lucas_base() {
+        let n = new::<2>(Some(128)).unwrap();
+        assert!(strong_check_base(&n, LucasBase::Selfridge));
+        assert!(strong_check_base(&n, LucasBase::BruceMethodA));
+        assert!(strong_check_base(&n, LucasBase::BruteForce));
+    }
+
+    #[test]
+    fn full_width_mode() {
+        let n = new::<4>(None).unwrap();
         assert!(check(&n));
         assert!(strong_check(&n));
     }